@@ -16,41 +16,33 @@ struct VectorAbsoluteMaxIndex<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to accumulate
-    n: usize,
-
     // OpenCl buffer to store the output imax vector
     imax_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output x vector
     x_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
 }
 
 trait RunVectorAbsoluteMaxIndex {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorAbsoluteMaxIndex<'a, T>) {
-    assert!(
-        params.imax_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorAbsoluteMaxIndex<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    params.x_vector.check_extent("amax", "x buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiSamax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -61,15 +53,15 @@ impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, f32> {
 
 impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiDamax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -80,15 +72,15 @@ impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, f64> {
 
 impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiCamax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -99,15 +91,15 @@ impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, Complex32> {
 
 impl<'a> RunVectorAbsoluteMaxIndex for VectorAbsoluteMaxIndex<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiZamax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -133,8 +125,32 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&x_vector)
             .imax_vector(&imax_vector)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
+
+    #[test]
+    fn test_matrix_views() {
+        use crate::{LayoutRowMajor, MatrixBuffer};
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(9).build().unwrap();
+        let matrix = MatrixBuffer::new_default(&pro_que, 3, 3, 0.0f32, LayoutRowMajor);
+        let values = vec![1.0, -2.0, 3.0, -4.0, -12.0, -6.0, 7.0, -8.0, 10.0];
+        matrix.buffer().write(&values).enq().unwrap();
+        let imax_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let imax_vector = VectorBuffer::builder().buffer(imax_buffer).build();
+
+        // CLBlast stores the index as an unsigned integer.
+        let mut result = vec![0.0f32; 9];
+        for (x_vector, expected) in [(matrix.column(0), 2), (matrix.diagonal(), 1)].iter() {
+            let task = VectorAbsoluteMaxIndex::builder()
+                .queue(&pro_que.queue())
+                .x_vector(x_vector)
+                .imax_vector(&imax_vector)
+                .build();
+            unsafe { task.run().unwrap() }
+            imax_vector.buffer().read(&mut result).enq().unwrap();
+            assert_eq!(result[0].to_bits(), *expected);
+        }
+    }
 }
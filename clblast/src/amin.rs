@@ -16,41 +16,33 @@ struct VectorAbsoluteMinIndex<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to accumulate
-    n: usize,
-
     // OpenCl buffer to store the output imin vector
     imin_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output x vector
     x_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
 }
 
 trait RunVectorAbsoluteMinIndex {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorAbsoluteMinIndex<'a, T>) {
-    assert!(
-        params.imin_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorAbsoluteMinIndex<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    params.x_vector.check_extent("amin", "x buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiSamin(
-            self.n as u64,
+            n as u64,
             self.imin_vector.buffer.as_ptr(),
             self.imin_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -61,15 +53,15 @@ impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, f32> {
 
 impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiDamin(
-            self.n as u64,
+            n as u64,
             self.imin_vector.buffer.as_ptr(),
             self.imin_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -80,15 +72,15 @@ impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, f64> {
 
 impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiCamin(
-            self.n as u64,
+            n as u64,
             self.imin_vector.buffer.as_ptr(),
             self.imin_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -99,15 +91,15 @@ impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, Complex32> {
 
 impl<'a> RunVectorAbsoluteMinIndex for VectorAbsoluteMinIndex<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiZamin(
-            self.n as u64,
+            n as u64,
             self.imin_vector.buffer.as_ptr(),
             self.imin_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -133,7 +125,6 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&x_vector)
             .imin_vector(&imin_vector)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
@@ -16,41 +16,33 @@ struct VectorAbsoluteSum<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to accumulate
-    n: usize,
-
     // OpenCl buffer to store the output x vector
     asum_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output y vector
     x_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
 }
 
 trait RunVectorAbsoluteSum {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorAbsoluteSum<'a, T>) {
-    assert!(
-        params.asum_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorAbsoluteSum<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    params.x_vector.check_extent("asum", "x buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastSasum(
-            self.n as u64,
+            n as u64,
             self.asum_vector.buffer.as_ptr(),
             self.asum_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -61,15 +53,15 @@ impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, f32> {
 
 impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDasum(
-            self.n as u64,
+            n as u64,
             self.asum_vector.buffer.as_ptr(),
             self.asum_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -80,15 +72,15 @@ impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, f64> {
 
 impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastScasum(
-            self.n as u64,
+            n as u64,
             self.asum_vector.buffer.as_ptr(),
             self.asum_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -99,15 +91,15 @@ impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, Complex32> {
 
 impl<'a> RunVectorAbsoluteSum for VectorAbsoluteSum<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDzasum(
-            self.n as u64,
+            n as u64,
             self.asum_vector.buffer.as_ptr(),
             self.asum_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -133,8 +125,31 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&x_vector)
             .asum_vector(&a_sum)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
+
+    #[test]
+    fn test_matrix_views() {
+        use crate::{LayoutRowMajor, MatrixBuffer};
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(9).build().unwrap();
+        let matrix = MatrixBuffer::new_default(&pro_que, 3, 3, 0.0f32, LayoutRowMajor);
+        let values = vec![1.0, -2.0, 3.0, -4.0, -12.0, -6.0, 7.0, -8.0, 10.0];
+        matrix.buffer().write(&values).enq().unwrap();
+        let asum_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let a_sum = VectorBuffer::builder().buffer(asum_buffer).build();
+
+        let mut result = vec![0.0; 9];
+        for (x_vector, expected) in [(matrix.column(1), 22.0), (matrix.diagonal(), 23.0)].iter() {
+            let task = VectorAbsoluteSum::builder()
+                .queue(&pro_que.queue())
+                .x_vector(x_vector)
+                .asum_vector(&a_sum)
+                .build();
+            unsafe { task.run().unwrap() }
+            a_sum.buffer().read(&mut result).enq().unwrap();
+            assert_eq!(result[0], *expected);
+        }
+    }
 }
@@ -0,0 +1,227 @@
+use std::ptr;
+
+use num_complex::{Complex32, Complex64};
+use ocl::{OclPrm, Queue};
+
+use crate::{strided_end, Error, ReprSys, VectorBuffer};
+
+use typed_builder::TypedBuilder;
+
+use clblast_sys::{
+    CLBlastCaxpyBatched, CLBlastDaxpyBatched, CLBlastSaxpyBatched, CLBlastZaxpyBatched,
+};
+
+/// Batched version of axpy: Performs `y[i] = alphas[i] * x[i] + y[i]` for every batch `i` in a single call.
+///
+/// All batches share the `x` and `y` buffers, batch `i` starts `x_offsets[i]` (`y_offsets[i]`) values
+/// after the offset of the respective [`VectorBuffer`].
+#[derive(TypedBuilder)]
+pub struct AxpyBatched<'a, T: OclPrm> {
+    /// OpenCL command queue associated with a context and device to execute the routine on.
+    queue: &'a Queue,
+
+    /// Input scalar constants `alpha`, one per batch
+    alphas: &'a [T],
+
+    /// number of values per batch
+    n: usize,
+
+    // OpenCl buffer containing the x vectors
+    x_vector: &'a VectorBuffer<T>,
+    /// Start of the x vector of every batch
+    x_offsets: &'a [usize],
+    // OpenCl buffer to store the output y vectors
+    y_vector: &'a VectorBuffer<T>,
+    /// Start of the y vector of every batch
+    y_offsets: &'a [usize],
+
+    /// Stride/increment of the input x vectors. This value must be greater than 0. Defaults to the increment of the x vector.
+    #[builder(default = x_vector.inc)]
+    x_stride: usize,
+    /// Stride/increment of the output y vectors. This value must be greater than 0. Defaults to the increment of the y vector.
+    #[builder(default = y_vector.inc)]
+    y_stride: usize,
+}
+
+pub trait RunAxpyBatched {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+/// Checks the batch sizes and returns the absolute offsets of the x and y vectors of every batch
+fn check_dimensions<'a, T: OclPrm>(
+    params: &AxpyBatched<'a, T>,
+) -> Result<(Vec<u64>, Vec<u64>), Error> {
+    let batch_count = params.alphas.len();
+    Error::check_min_dimension("axpy_batched", "alphas.len() (batch_count)", 1, batch_count)?;
+    Error::check_dimension(
+        "axpy_batched",
+        "x_offsets.len()",
+        batch_count,
+        params.x_offsets.len(),
+    )?;
+    Error::check_dimension(
+        "axpy_batched",
+        "y_offsets.len()",
+        batch_count,
+        params.y_offsets.len(),
+    )?;
+
+    let x_offsets = batch_offsets(
+        params.x_vector,
+        params.x_offsets,
+        params.n,
+        params.x_stride,
+        "x buffer",
+    )?;
+    let y_offsets = batch_offsets(
+        params.y_vector,
+        params.y_offsets,
+        params.n,
+        params.y_stride,
+        "y buffer",
+    )?;
+    Ok((x_offsets, y_offsets))
+}
+
+fn batch_offsets<T: OclPrm>(
+    vector: &VectorBuffer<T>,
+    offsets: &[usize],
+    n: usize,
+    stride: usize,
+    operand: &'static str,
+) -> Result<Vec<u64>, Error> {
+    offsets
+        .iter()
+        .map(|offset| {
+            let overflow = || Error::Overflow {
+                routine: "axpy_batched",
+                operand,
+            };
+            let start = vector.offset.checked_add(*offset).ok_or_else(overflow)?;
+            let end = strided_end(start, n, stride).ok_or_else(overflow)?;
+            Error::check_min_dimension("axpy_batched", operand, end, vector.buffer.len())?;
+            Ok(start as u64)
+        })
+        .collect()
+}
+
+impl<'a> RunAxpyBatched for AxpyBatched<'a, f32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let (x_offsets, y_offsets) = check_dimensions(&self)?;
+
+        let res = CLBlastSaxpyBatched(
+            self.n as u64,
+            self.alphas.as_ptr(),
+            self.x_vector.buffer.as_ptr(),
+            x_offsets.as_ptr(),
+            self.x_stride as u64,
+            self.y_vector.buffer.as_ptr(),
+            y_offsets.as_ptr(),
+            self.y_stride as u64,
+            self.alphas.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunAxpyBatched for AxpyBatched<'a, f64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let (x_offsets, y_offsets) = check_dimensions(&self)?;
+
+        let res = CLBlastDaxpyBatched(
+            self.n as u64,
+            self.alphas.as_ptr(),
+            self.x_vector.buffer.as_ptr(),
+            x_offsets.as_ptr(),
+            self.x_stride as u64,
+            self.y_vector.buffer.as_ptr(),
+            y_offsets.as_ptr(),
+            self.y_stride as u64,
+            self.alphas.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunAxpyBatched for AxpyBatched<'a, Complex32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let (x_offsets, y_offsets) = check_dimensions(&self)?;
+        let alphas = self.alphas.iter().map(|a| a.to_c()).collect::<Vec<_>>();
+
+        let res = CLBlastCaxpyBatched(
+            self.n as u64,
+            alphas.as_ptr(),
+            self.x_vector.buffer.as_ptr(),
+            x_offsets.as_ptr(),
+            self.x_stride as u64,
+            self.y_vector.buffer.as_ptr(),
+            y_offsets.as_ptr(),
+            self.y_stride as u64,
+            alphas.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunAxpyBatched for AxpyBatched<'a, Complex64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let (x_offsets, y_offsets) = check_dimensions(&self)?;
+        let alphas = self.alphas.iter().map(|a| a.to_c()).collect::<Vec<_>>();
+
+        let res = CLBlastZaxpyBatched(
+            self.n as u64,
+            alphas.as_ptr(),
+            self.x_vector.buffer.as_ptr(),
+            x_offsets.as_ptr(),
+            self.x_stride as u64,
+            self.y_vector.buffer.as_ptr(),
+            y_offsets.as_ptr(),
+            self.y_stride as u64,
+            alphas.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::VectorBuffer;
+
+    #[test]
+    fn test_float() {
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let x_buffer = pro_que.buffer_builder().fill_val(1.0f32).build().unwrap();
+        let y_buffer = pro_que.buffer_builder().fill_val(0.0f32).build().unwrap();
+        let x_vector = VectorBuffer::builder().buffer(x_buffer).build();
+        let y_vector = VectorBuffer::builder().buffer(y_buffer).build();
+        let task = AxpyBatched::builder()
+            .queue(&pro_que.queue())
+            .alphas(&[1.0, 2.0])
+            .n(10)
+            .x_vector(&x_vector)
+            .x_offsets(&[0, 10])
+            .y_vector(&y_vector)
+            .y_offsets(&[0, 10])
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![0.0; 20];
+        y_vector.buffer.read(&mut out[..]).enq().unwrap();
+        assert_eq!(out[..10], vec![1.0; 10][..]);
+        assert_eq!(out[10..], vec![2.0; 10][..]);
+    }
+}
@@ -0,0 +1,166 @@
+//! Control over clblast's cache of compiled kernels.
+//!
+//! clblast compiles its OpenCL kernels lazily, the first time a routine is run for a device and precision.
+//! This can take seconds, which is why latency sensitive applications may want to pay that cost upfront.
+
+use num_complex::{Complex32, Complex64};
+use ocl::core::ClDeviceIdPtr;
+use ocl::{Buffer, Device, OclPrm, Queue};
+
+use clblast_sys::{CLBlastClearCache, CLBlastFillCache};
+
+use crate::axpy::{RunVectorAxpy, VectorAxpy};
+use crate::copy::{RunVectorCopy, VectorCopy};
+use crate::dot::{RunVectorDot, VectorDot};
+use crate::gemm::{Gemm, RunGemm};
+use crate::scal::{RunVectorScale, VectorScale};
+use crate::swap::{RunVectorSwap, VectorSwap};
+use crate::{
+    BlasError, Error, LayoutRowMajor, MatrixBuffer, NeutralAdd, NeutralMul, Precision, VectorBuffer,
+};
+
+/// Removes all compiled kernels from the cache, for all devices.
+pub fn clear_cache() -> Result<(), Error> {
+    let res = unsafe { CLBlastClearCache() };
+    Error::from_c_either(res)
+}
+
+/// Compiles the kernels of all routines and precisions for `device` and stores them in the cache.
+pub fn fill_cache(device: &Device) -> Result<(), Error> {
+    let res = unsafe { CLBlastFillCache(device.as_ptr()) };
+    Error::from_c_either(res)
+}
+
+/// Routines which can be warmed up selectively, see [`warm_up`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Routine {
+    Gemm,
+    Axpy,
+    Copy,
+    Dot,
+    Scal,
+    Swap,
+}
+
+/// Compiles the kernels of only the given routine/precision combinations, by running each of them once
+/// on a tiny input on `queue`.
+///
+/// Cheaper than [`fill_cache`] if only a few routines are used. [`Precision::Half`] is not supported and
+/// results in [`BlasError::NotImplemented`].
+pub fn warm_up(queue: &Queue, routines: &[(Routine, Precision)]) -> Result<(), Error> {
+    for (routine, precision) in routines {
+        unsafe {
+            match precision {
+                Precision::Half => {
+                    return Err(Error::Blas {
+                        source: BlasError::NotImplemented,
+                    })
+                }
+                Precision::Single => warm_up_routine::<f32>(queue, routine)?,
+                Precision::Double => warm_up_routine::<f64>(queue, routine)?,
+                Precision::ComplexSingle => warm_up_routine::<Complex32>(queue, routine)?,
+                Precision::ComplexDouble => warm_up_routine::<Complex64>(queue, routine)?,
+            }
+        }
+    }
+    queue.finish()?;
+    Ok(())
+}
+
+fn warm_up_vector<T: OclPrm + NeutralAdd>(queue: &Queue) -> Result<VectorBuffer<T>, Error> {
+    let buffer = Buffer::<T>::builder()
+        .queue(queue.clone())
+        .len(1)
+        .fill_val(T::ZERO)
+        .build()?;
+    Ok(VectorBuffer::builder().buffer(buffer).build())
+}
+
+unsafe fn warm_up_routine<T>(queue: &Queue, routine: &Routine) -> Result<(), Error>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    for<'a> Gemm<'a, T, LayoutRowMajor, LayoutRowMajor, LayoutRowMajor>: RunGemm,
+    for<'a> VectorAxpy<'a, T>: RunVectorAxpy,
+    for<'a> VectorCopy<'a, T>: RunVectorCopy,
+    for<'a> VectorDot<'a, T>: RunVectorDot,
+    for<'a> VectorScale<'a, T>: RunVectorScale,
+    for<'a> VectorSwap<'a, T>: RunVectorSwap,
+{
+    let x_vector = warm_up_vector::<T>(queue)?;
+    let y_vector = warm_up_vector::<T>(queue)?;
+
+    match routine {
+        Routine::Gemm => {
+            let a = MatrixBuffer::new(1, 1, x_vector.buffer.clone(), LayoutRowMajor);
+            let b = MatrixBuffer::new(1, 1, y_vector.buffer.clone(), LayoutRowMajor);
+            let mut c = MatrixBuffer::new(1, 1, warm_up_vector::<T>(queue)?.buffer, LayoutRowMajor);
+            Gemm::builder()
+                .queue(queue)
+                .a(&a)
+                .b(&b)
+                .c(&mut c)
+                .build()
+                .run()
+        }
+        Routine::Axpy => VectorAxpy::builder()
+            .queue(queue)
+            .alpha(T::ONE)
+            .x_vector(&x_vector)
+            .y_vector(&y_vector)
+            .build()
+            .run(),
+        Routine::Copy => VectorCopy::builder()
+            .queue(queue)
+            .x_vector(&x_vector)
+            .y_vector(&y_vector)
+            .build()
+            .run(),
+        Routine::Dot => VectorDot::builder()
+            .queue(queue)
+            .dot_buffer(&warm_up_vector::<T>(queue)?)
+            .x_vector(&x_vector)
+            .y_vector(&y_vector)
+            .build()
+            .run(),
+        Routine::Scal => VectorScale::builder()
+            .queue(queue)
+            .alpha(T::ONE)
+            .x_vector(&x_vector)
+            .build()
+            .run(),
+        Routine::Swap => VectorSwap::builder()
+            .queue(queue)
+            .x_vector(&x_vector)
+            .y_vector(&y_vector)
+            .build()
+            .run(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_warm_up() {
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        clear_cache().unwrap();
+        warm_up(
+            &pro_que.queue(),
+            &[
+                (Routine::Gemm, Precision::Single),
+                (Routine::Axpy, Precision::Double),
+                (Routine::Dot, Precision::ComplexSingle),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_fill_cache() {
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        fill_cache(&pro_que.device()).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+use std::ptr;
+
+use num_complex::{Complex32, Complex64};
+use ocl::{OclPrm, Queue};
+
+use crate::im2col::Im2colDescriptor;
+use crate::{Error, KernelMode, ReprSys, VectorBuffer, VectorViewMut};
+
+use typed_builder::TypedBuilder;
+
+use clblast_sys::{CLBlastCcol2im, CLBlastDcol2im, CLBlastScol2im, CLBlastZcol2im};
+
+/// Column to image transform: Counterpart of [`crate::im2col::Im2col`]. Accumulates the columns back into the image,
+/// values of overlapping patches are summed up. This is e.g. the gradient of im2col during backpropagation.
+#[derive(TypedBuilder)]
+pub struct Col2im<'a, T: OclPrm> {
+    /// OpenCL command queue associated with a context and device to execute the routine on.
+    queue: &'a Queue,
+
+    /// image and kernel geometry
+    descriptor: &'a Im2colDescriptor,
+
+    #[builder(default = KernelMode::CrossCorrelation)]
+    kernel_mode: KernelMode,
+
+    // OpenCl buffer containing the input columns
+    col_buffer: &'a VectorBuffer<T>,
+    // OpenCl buffer to store the output image
+    #[builder(setter(into))]
+    im_buffer: VectorViewMut<'a, T>,
+}
+
+pub trait RunCol2im {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+fn check_dimensions<'a, T: OclPrm>(params: &Col2im<'a, T>) -> Result<(), Error> {
+    // CLBlast reads and writes both buffers contiguously, starting at their offsets
+    let col_size = params.descriptor.col_size()?;
+    Error::check_dimension("col2im", "col_buffer.inc", 1, params.col_buffer.inc)?;
+    Error::check_min_dimension("col2im", "col_buffer.len", col_size, params.col_buffer.len)?;
+    params.col_buffer.check_extent("col2im", "col buffer")?;
+
+    let image_size = params.descriptor.image_size()?;
+    Error::check_dimension("col2im", "im_buffer.inc", 1, params.im_buffer.inc)?;
+    Error::check_min_dimension("col2im", "im_buffer.len", image_size, params.im_buffer.len)?;
+    params.im_buffer.check_extent("col2im", "im buffer")
+}
+
+impl<'a> RunCol2im for Col2im<'a, f32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastScol2im(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunCol2im for Col2im<'a, f64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastDcol2im(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunCol2im for Col2im<'a, Complex32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastCcol2im(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunCol2im for Col2im<'a, Complex64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastZcol2im(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::VectorBuffer;
+
+    #[test]
+    fn test_float() {
+        use ocl::ProQue;
+        let descriptor = Im2colDescriptor::builder()
+            .channels(1)
+            .height(4)
+            .width(4)
+            .kernel_h(3)
+            .kernel_w(3)
+            .build();
+        let pro_que = ProQue::builder().src("").dims(36).build().unwrap();
+        let col_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let im_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let col_vector = VectorBuffer::builder().buffer(col_buffer).build();
+        let mut im_vector = VectorBuffer::builder().buffer(im_buffer).build();
+        let task = Col2im::builder()
+            .queue(&pro_que.queue())
+            .descriptor(&descriptor)
+            .col_buffer(&col_vector)
+            .im_buffer(&mut im_vector)
+            .build();
+        unsafe { task.run().unwrap() }
+    }
+}
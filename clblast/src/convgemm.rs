@@ -0,0 +1,298 @@
+use std::ptr;
+
+use ocl::{OclPrm, Queue};
+
+use crate::im2col::Im2colDescriptor;
+use crate::{Error, KernelMode, ReprSys, VectorBuffer, VectorViewMut};
+
+use typed_builder::TypedBuilder;
+
+use clblast_sys::{CLBlastDconvgemm, CLBlastSconvgemm};
+
+/// Describes the geometry of a batched 2D convolution computed by [`ConvGemm`]
+///
+/// - the input holds `batch_count` images in NCHW order (`channels` x `height` x `width`)
+/// - the kernel holds `num_kernels` kernels of `channels` x `kernel_h` x `kernel_w` values
+/// - the result holds `batch_count` x `num_kernels` x `output_height()` x `output_width()` values
+#[derive(TypedBuilder, Clone, Debug, PartialEq, Eq)]
+pub struct ConvGemmDescriptor {
+    /// number of channels of the input images
+    pub channels: usize,
+    /// height of the input images
+    pub height: usize,
+    /// width of the input images
+    pub width: usize,
+
+    /// height of the kernels
+    pub kernel_h: usize,
+    /// width of the kernels
+    pub kernel_w: usize,
+
+    /// zero-padding added to the top and bottom of the images
+    #[builder(default = 0)]
+    pub pad_h: usize,
+    /// zero-padding added to the left and right of the images
+    #[builder(default = 0)]
+    pub pad_w: usize,
+
+    /// vertical distance between two kernel applications
+    #[builder(default = 1)]
+    pub stride_h: usize,
+    /// horizontal distance between two kernel applications
+    #[builder(default = 1)]
+    pub stride_w: usize,
+
+    /// vertical distance between two kernel elements
+    #[builder(default = 1)]
+    pub dilation_h: usize,
+    /// horizontal distance between two kernel elements
+    #[builder(default = 1)]
+    pub dilation_w: usize,
+
+    /// number of kernels, i.e. number of output channels
+    pub num_kernels: usize,
+
+    /// number of images in the input
+    #[builder(default = 1)]
+    pub batch_count: usize,
+}
+
+impl ConvGemmDescriptor {
+    /// geometry of a single image of the batch
+    pub fn im2col(&self) -> Im2colDescriptor {
+        Im2colDescriptor {
+            channels: self.channels,
+            height: self.height,
+            width: self.width,
+            kernel_h: self.kernel_h,
+            kernel_w: self.kernel_w,
+            pad_h: self.pad_h,
+            pad_w: self.pad_w,
+            stride_h: self.stride_h,
+            stride_w: self.stride_w,
+            dilation_h: self.dilation_h,
+            dilation_w: self.dilation_w,
+        }
+    }
+
+    /// height of the result images
+    pub fn output_height(&self) -> Result<usize, Error> {
+        self.im2col().output_height()
+    }
+
+    /// width of the result images
+    pub fn output_width(&self) -> Result<usize, Error> {
+        self.im2col().output_width()
+    }
+
+    /// number of values in the input buffer
+    pub fn input_size(&self) -> Result<usize, Error> {
+        let image_size = self.im2col().image_size()?;
+        self.batch_count
+            .checked_mul(image_size)
+            .ok_or(Error::Overflow {
+                routine: DESCRIPTOR,
+                operand: "input size",
+            })
+    }
+
+    /// number of values in the kernel buffer
+    pub fn kernel_size(&self) -> Result<usize, Error> {
+        self.num_kernels
+            .checked_mul(self.channels)
+            .and_then(|size| size.checked_mul(self.kernel_h))
+            .and_then(|size| size.checked_mul(self.kernel_w))
+            .ok_or(Error::Overflow {
+                routine: DESCRIPTOR,
+                operand: "kernel size",
+            })
+    }
+
+    /// number of values in the result buffer
+    pub fn result_size(&self) -> Result<usize, Error> {
+        let (output_height, output_width) = (self.output_height()?, self.output_width()?);
+        self.batch_count
+            .checked_mul(self.num_kernels)
+            .and_then(|size| size.checked_mul(output_height))
+            .and_then(|size| size.checked_mul(output_width))
+            .ok_or(Error::Overflow {
+                routine: DESCRIPTOR,
+                operand: "result size",
+            })
+    }
+}
+
+const DESCRIPTOR: &str = "ConvGemmDescriptor";
+
+/// Batched 2D convolution as GEMM: Convolves each of the `batch_count` input images with each of the
+/// `num_kernels` kernels, without materializing the im2col columns.
+///
+/// Only `f32` and `f64` are supported: CLBlast also provides a half precision `Hconvgemm`,
+/// but this crate has no half precision element type to wrap it with.
+#[derive(TypedBuilder)]
+pub struct ConvGemm<'a, T: OclPrm> {
+    /// OpenCL command queue associated with a context and device to execute the routine on.
+    queue: &'a Queue,
+
+    /// image, kernel and batch geometry
+    descriptor: &'a ConvGemmDescriptor,
+
+    #[builder(default = KernelMode::CrossCorrelation)]
+    kernel_mode: KernelMode,
+
+    // OpenCl buffer containing the input images
+    im_buffer: &'a VectorBuffer<T>,
+    // OpenCl buffer containing the kernels
+    kernel_buffer: &'a VectorBuffer<T>,
+    // OpenCl buffer to store the result
+    #[builder(setter(into))]
+    result_buffer: VectorViewMut<'a, T>,
+}
+
+pub trait RunConvGemm {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+fn check_dimensions<'a, T: OclPrm>(params: &ConvGemm<'a, T>) -> Result<(), Error> {
+    // CLBlast reads and writes all buffers contiguously, starting at their offsets
+    let input_size = params.descriptor.input_size()?;
+    Error::check_dimension("convgemm", "im_buffer.inc", 1, params.im_buffer.inc)?;
+    Error::check_min_dimension(
+        "convgemm",
+        "im_buffer.len",
+        input_size,
+        params.im_buffer.len,
+    )?;
+    params.im_buffer.check_extent("convgemm", "im buffer")?;
+
+    let kernel_size = params.descriptor.kernel_size()?;
+    Error::check_dimension("convgemm", "kernel_buffer.inc", 1, params.kernel_buffer.inc)?;
+    Error::check_min_dimension(
+        "convgemm",
+        "kernel_buffer.len",
+        kernel_size,
+        params.kernel_buffer.len,
+    )?;
+    params
+        .kernel_buffer
+        .check_extent("convgemm", "kernel buffer")?;
+
+    let result_size = params.descriptor.result_size()?;
+    Error::check_dimension("convgemm", "result_buffer.inc", 1, params.result_buffer.inc)?;
+    Error::check_min_dimension(
+        "convgemm",
+        "result_buffer.len",
+        result_size,
+        params.result_buffer.len,
+    )?;
+    params
+        .result_buffer
+        .check_extent("convgemm", "result buffer")
+}
+
+impl<'a> RunConvGemm for ConvGemm<'a, f32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastSconvgemm(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            d.num_kernels as u64,
+            d.batch_count as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            self.kernel_buffer.buffer.as_ptr(),
+            self.kernel_buffer.offset as u64,
+            self.result_buffer.buffer.as_ptr(),
+            self.result_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunConvGemm for ConvGemm<'a, f64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastDconvgemm(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            d.num_kernels as u64,
+            d.batch_count as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            self.kernel_buffer.buffer.as_ptr(),
+            self.kernel_buffer.offset as u64,
+            self.result_buffer.buffer.as_ptr(),
+            self.result_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::VectorBuffer;
+
+    #[test]
+    fn test_float() {
+        use ocl::ProQue;
+        let descriptor = ConvGemmDescriptor::builder()
+            .channels(2)
+            .height(4)
+            .width(4)
+            .kernel_h(3)
+            .kernel_w(3)
+            .num_kernels(3)
+            .batch_count(2)
+            .build();
+        assert_eq!(descriptor.input_size().unwrap(), 64);
+        assert_eq!(descriptor.kernel_size().unwrap(), 54);
+        assert_eq!(descriptor.result_size().unwrap(), 24);
+
+        let pro_que = ProQue::builder().src("").dims(64).build().unwrap();
+        let im_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let kernel_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let result_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let im_vector = VectorBuffer::builder().buffer(im_buffer).build();
+        let kernel_vector = VectorBuffer::builder().buffer(kernel_buffer).build();
+        let mut result_vector = VectorBuffer::builder().buffer(result_buffer).build();
+        let task = ConvGemm::builder()
+            .queue(&pro_que.queue())
+            .descriptor(&descriptor)
+            .im_buffer(&im_vector)
+            .kernel_buffer(&kernel_vector)
+            .result_buffer(&mut result_vector)
+            .build();
+        unsafe { task.run().unwrap() }
+    }
+}
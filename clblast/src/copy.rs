@@ -11,53 +11,40 @@ use clblast_sys::{CLBlastCcopy, CLBlastDcopy, CLBlastScopy, CLBlastZcopy};
 
 /// Copies the contents of vector x into vector y.
 #[derive(TypedBuilder)]
-struct VectorCopy<'a, T: OclPrm> {
+pub(crate) struct VectorCopy<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to swap
-    n: usize,
-
     // OpenCl buffer to store the output x vector
     x_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output y vector
     y_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
-    /// Stride/increment of the output y vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    y_stride: usize,
 }
 
-trait RunVectorCopy {
+pub(crate) trait RunVectorCopy {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorCopy<'a, T>) {
-    assert!(
-        params.x_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
-    assert!(
-        params.y_vector.buffer.len() > params.n * params.y_stride,
-        "y buffer is too short for n and y_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorCopy<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    Error::check_dimension("copy", "y.len (n)", n, params.y_vector.len)?;
+    params.x_vector.check_extent("copy", "x buffer")?;
+    params.y_vector.check_extent("copy", "y buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorCopy for VectorCopy<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastScopy(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -68,16 +55,16 @@ impl<'a> RunVectorCopy for VectorCopy<'a, f32> {
 
 impl<'a> RunVectorCopy for VectorCopy<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDcopy(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -88,16 +75,16 @@ impl<'a> RunVectorCopy for VectorCopy<'a, f64> {
 
 impl<'a> RunVectorCopy for VectorCopy<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastCcopy(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -108,16 +95,16 @@ impl<'a> RunVectorCopy for VectorCopy<'a, Complex32> {
 
 impl<'a> RunVectorCopy for VectorCopy<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastZcopy(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -143,7 +130,6 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&a_matrix)
             .y_vector(&b_matrix)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
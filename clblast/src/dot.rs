@@ -11,13 +11,10 @@ use clblast_sys::{CLBlastCdotu, CLBlastDdot, CLBlastSdot, CLBlastZdotu};
 
 /// Multiplies n elements of the vectors x and y element-wise and accumulates the results. The sum is stored in the dot buffer.
 #[derive(TypedBuilder)]
-struct VectorDot<'a, T: OclPrm> {
+pub(crate) struct VectorDot<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to swap
-    n: usize,
-
     // OpenCl buffer to store the result in
     dot_buffer: &'a VectorBuffer<T>,
 
@@ -25,44 +22,34 @@ struct VectorDot<'a, T: OclPrm> {
     x_vector: &'a VectorBuffer<T>,
     // OpenCl buffer containing the y vector
     y_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
-    /// Stride/increment of the output y vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    y_stride: usize,
 }
 
-trait RunVectorDot {
+pub(crate) trait RunVectorDot {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorDot<'a, T>) {
-    assert!(
-        params.x_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
-    assert!(
-        params.y_vector.buffer.len() > params.n * params.y_stride,
-        "y buffer is too short for n and y_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorDot<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    Error::check_dimension("dot", "y.len (n)", n, params.y_vector.len)?;
+    params.x_vector.check_extent("dot", "x buffer")?;
+    params.y_vector.check_extent("dot", "y buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorDot for VectorDot<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastSdot(
-            self.n as u64,
+            n as u64,
             self.dot_buffer.buffer.as_ptr(),
             self.dot_buffer.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -73,18 +60,18 @@ impl<'a> RunVectorDot for VectorDot<'a, f32> {
 
 impl<'a> RunVectorDot for VectorDot<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDdot(
-            self.n as u64,
+            n as u64,
             self.dot_buffer.buffer.as_ptr(),
             self.dot_buffer.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -96,18 +83,18 @@ impl<'a> RunVectorDot for VectorDot<'a, f64> {
 /// called `xDOTU` in clblast: Dot product of two complex vectors
 impl<'a> RunVectorDot for VectorDot<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastCdotu(
-            self.n as u64,
+            n as u64,
             self.dot_buffer.buffer.as_ptr(),
             self.dot_buffer.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -119,18 +106,18 @@ impl<'a> RunVectorDot for VectorDot<'a, Complex32> {
 /// called `xDOTU` in clblast: Dot product of two complex vectors
 impl<'a> RunVectorDot for VectorDot<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastZdotu(
-            self.n as u64,
+            n as u64,
             self.dot_buffer.buffer.as_ptr(),
             self.dot_buffer.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -159,7 +146,6 @@ mod test {
             .dot_buffer(&dot_vector)
             .x_vector(&a_vector)
             .y_vector(&b_vector)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
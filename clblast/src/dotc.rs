@@ -15,9 +15,6 @@ struct VectorDotConjucate<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to swap
-    n: usize,
-
     // OpenCl buffer to store the result in
     dot_buffer: &'a VectorBuffer<T>,
 
@@ -25,46 +22,36 @@ struct VectorDotConjucate<'a, T: OclPrm> {
     x_vector: &'a VectorBuffer<T>,
     // OpenCl buffer containing the y vector
     y_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
-    /// Stride/increment of the output y vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    y_stride: usize,
 }
 
 trait RunVectorDot {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorDotConjucate<'a, T>) {
-    assert!(
-        params.x_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
-    assert!(
-        params.y_vector.buffer.len() > params.n * params.y_stride,
-        "y buffer is too short for n and y_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorDotConjucate<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    Error::check_dimension("dotc", "y.len (n)", n, params.y_vector.len)?;
+    params.x_vector.check_extent("dotc", "x buffer")?;
+    params.y_vector.check_extent("dotc", "y buffer")?;
+    Ok(n)
 }
 
 
 /// called `xDOTC` in clblast: Dot product of two complex vectors, one conjugated
 impl<'a> RunVectorDot for VectorDotConjucate<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastCdotc(
-            self.n as u64,
+            n as u64,
             self.dot_buffer.buffer.as_ptr(),
             self.dot_buffer.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -76,18 +63,18 @@ impl<'a> RunVectorDot for VectorDotConjucate<'a, Complex32> {
 /// called `xDOTC` in clblast: Dot product of two complex vectors, one conjugated
 impl<'a> RunVectorDot for VectorDotConjucate<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastZdotc(
-            self.n as u64,
+            n as u64,
             self.dot_buffer.buffer.as_ptr(),
             self.dot_buffer.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -116,7 +103,6 @@ mod test {
             .dot_buffer(&dot_vector)
             .x_vector(&a_vector)
             .y_vector(&b_vector)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
@@ -1,24 +1,37 @@
+use std::mem;
 use std::ptr;
 
 use num_complex::Complex32;
 use num_complex::Complex64;
+use ocl::ffi::cl_mem;
+use ocl::Buffer;
 use ocl::OclPrm;
 use ocl::Queue;
 use typed_builder::TypedBuilder;
 
 use clblast_sys::cl_float2;
-use clblast_sys::CLBlastCgemm;
-use clblast_sys::CLBlastDgemm;
-use clblast_sys::CLBlastSgemm;
-use clblast_sys::CLBlastZgemm;
-
+use clblast_sys::CLBlastCGemmTempBufferSize;
+use clblast_sys::CLBlastCgemmWithTempBuffer;
+use clblast_sys::CLBlastDGemmTempBufferSize;
+use clblast_sys::CLBlastDgemmWithTempBuffer;
+use clblast_sys::CLBlastSGemmTempBufferSize;
+use clblast_sys::CLBlastSgemmWithTempBuffer;
+use clblast_sys::CLBlastZGemmTempBufferSize;
+use clblast_sys::CLBlastZgemmWithTempBuffer;
+
+use crate::omatcopy::omatcopy_new;
+use crate::omatcopy::Omatcopy;
+use crate::omatcopy::RunOmatcopy;
 use crate::Error;
-use crate::MatrixBuffer;
+use crate::HostScalar;
+use crate::Layout;
 use crate::MatrixLayout;
 use crate::MatrixTranspose;
+use crate::MatrixViewMut;
 use crate::NeutralAdd;
 use crate::NeutralMul;
 use crate::ReprSys;
+use crate::TransposedView;
 
 /// Computes `C := alpha * A * B + beta * C`
 ///
@@ -27,24 +40,36 @@ use crate::ReprSys;
 /// - Matrix B: N⨯K (N Wide, K High)
 /// - Matrix C: M⨯N (N Wide, M High)
 ///
+/// A and B can be passed transposed with `.a(a.t())` or conjugate transposed with `.a(a.h())`,
+/// the shapes above then refer to the transposed matrices.
+///
+/// A and B may be stored in another layout than C: a matrix read in the other layout is its transpose,
+/// so their transpose flags are flipped instead. Only a conjugate transposed complex operand in another layout
+/// is first copied to the device in transposed form (see [`omatcopy_new`]).
+///
 /// ![](https://cnugteren.github.io/tutorial/images/gemm1.png)
 ///
 /// For details see: <https://cnugteren.github.io/tutorial/pages/page2.html>
 ///
 /// See also: <https://petewarden.com/2015/10/25/an-engineers-guide-to-gemm/>
 #[derive(TypedBuilder)]
-pub struct Gemm<'a, T, L>
+pub struct Gemm<'a, T, LA, LB, LC>
 where
     T: OclPrm + NeutralAdd + NeutralMul,
-    L: MatrixLayout,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
 {
     //Queue
     queue: &'a Queue,
 
     // Matrices
-    a: &'a MatrixBuffer<T, L>,
-    b: &'a MatrixBuffer<T, L>,
-    c: &'a mut MatrixBuffer<T, L>,
+    #[builder(setter(into))]
+    a: TransposedView<'a, T, LA>,
+    #[builder(setter(into))]
+    b: TransposedView<'a, T, LB>,
+    #[builder(setter(into))]
+    c: MatrixViewMut<'a, T, LC>,
 
     // factors
     #[builder(default=NeutralMul::ONE)]
@@ -52,174 +77,468 @@ where
     #[builder(default=NeutralAdd::ZERO)]
     beta: T,
 
-    // transpose
-    #[builder(default=MatrixTranspose::No)]
+    /// Workspace used by clblast instead of allocating one internally.
+    /// Must hold at least [`RunGemm::temp_buffer_size`] bytes.
+    #[builder(default, setter(strip_option))]
+    temp_buffer: Option<&'a Buffer<T>>,
+}
+
+fn check_dimensions<T, LA, LB, LC>(
+    params: &Gemm<T, LA, LB, LC>,
+) -> Result<(usize, usize, usize), Error>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
+{
+    gemm_dimensions(
+        "gemm",
+        (params.a.matrix.rows, params.a.matrix.columns),
+        params.a.transpose,
+        (params.b.matrix.rows, params.b.matrix.columns),
+        params.b.transpose,
+        (params.c.rows, params.c.columns),
+    )
+}
+
+/// Checks the shapes `(rows, columns)` of A, B and C of a gemm and returns `(k, n, m)`
+pub(crate) fn gemm_dimensions(
+    routine: &'static str,
+    a: (usize, usize),
     transpose_a: MatrixTranspose,
-    #[builder(default=MatrixTranspose::No)]
+    b: (usize, usize),
     transpose_b: MatrixTranspose,
+    c: (usize, usize),
+) -> Result<(usize, usize, usize), Error> {
+    // Performs the matrix product C = alpha * op(A) * op(B) + beta * C,
+    // in which op(A) (m rows by k columns) and op(B) (k rows by n columns) are two general rectangular input matrices,
+    // C (m rows by n column) is the matrix to be updated, and alpha and beta are scalar values.
+    // op(X) is X, its transpose or its conjugate transpose, so A and B are stored transposed if requested.
+
+    let (a_rows, a_columns) = transpose_a.apply(a.0, a.1);
+    let (b_rows, b_columns) = transpose_b.apply(b.0, b.1);
+    let (c_rows, c_columns) = c;
+
+    Error::check_dimension(routine, "op(b).rows (k)", a_columns, b_rows)?;
+    let k = a_columns;
+
+    Error::check_dimension(routine, "op(b).columns (n)", c_columns, b_columns)?;
+    let n = c_columns;
+
+    Error::check_dimension(routine, "op(a).rows (m)", c_rows, a_rows)?;
+    let m = c_rows;
+
+    Ok((k, n, m))
 }
 
-fn assert_dimensions<T: OclPrm + NeutralAdd + NeutralMul, L: MatrixLayout>(
-    params: &Gemm<T, L>,
-) -> (usize, usize, usize) {
-    // Performs the matrix product C = alpha * A * B + beta * C,
-    // in which A (m rows by k columns) and B (k rows by n columns) are two general rectangular input matrices,
-    // C (m rows by n column) is the matrix to be updated, and alpha and beta are scalar values.
-    // The matrices A and/or B can optionally be transposed before performing the operation.
+fn check_temp_buffer<T, LA, LB, LC>(
+    params: &Gemm<T, LA, LB, LC>,
+    required_size: usize,
+) -> Result<(), Error>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
+{
+    match params.temp_buffer {
+        Some(temp_buffer) => Error::check_min_dimension(
+            "gemm",
+            "temp buffer bytes",
+            required_size,
+            temp_buffer.len() * mem::size_of::<T>(),
+        ),
+        None => Ok(()),
+    }
+}
 
-    assert_eq!(params.a.columns, params.b.rows, "a.columns /= b.rows (k)");
-    let k = params.a.columns;
+fn temp_buffer_ptr<T, LA, LB, LC>(params: &Gemm<T, LA, LB, LC>) -> cl_mem
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
+{
+    match params.temp_buffer {
+        Some(temp_buffer) => temp_buffer.as_ptr(),
+        None => ptr::null_mut(),
+    }
+}
 
-    assert_eq!(
-        params.b.columns, params.c.columns,
-        "b.columns /= c.columns (n)"
-    );
-    let n = params.b.columns;
+/// How an operand of a gemm is passed to CLBlast in the layout of C
+struct Operand {
+    offset: usize,
+    stride: usize,
+    transpose: MatrixTranspose,
+    /// The operand is passed as a transposed copy of its adjoint, see [`Operand::buffer`]
+    adjoint: bool,
+}
 
-    assert_eq!(params.c.rows, params.a.rows, "c.columns /= a.rows (m)");
-    let m = params.c.rows;
+impl Operand {
+    /// Reads `view` in `layout`: a matrix stored in the other layout reads as its transpose.
+    /// The conjugate without transposition has no flag, so that case is passed as its adjoint.
+    fn map<T: HostScalar, L: MatrixLayout>(view: &TransposedView<T, L>, layout: Layout) -> Self {
+        let matrix = view.matrix;
+        let transpose = match view.transpose {
+            // the conjugate of a real matrix is the matrix itself, so it never needs the copy
+            MatrixTranspose::Conjugate if !T::COMPLEX => MatrixTranspose::Yes,
+            transpose => transpose,
+        };
+        let transpose = match (matrix.layout.layout() == layout, transpose) {
+            (true, transpose) => transpose,
+            (false, MatrixTranspose::No) => MatrixTranspose::Yes,
+            (false, MatrixTranspose::Yes) => MatrixTranspose::No,
+            (false, MatrixTranspose::Conjugate) => {
+                // the adjoint is a fresh columns x rows matrix
+                return Operand {
+                    offset: 0,
+                    stride: matrix.layout.default_stride(matrix.rows, matrix.columns),
+                    transpose: MatrixTranspose::Yes,
+                    adjoint: true,
+                };
+            }
+        };
+        Operand {
+            offset: matrix.offset,
+            stride: matrix.stride,
+            transpose,
+            adjoint: false,
+        }
+    }
 
-    (k, n, m)
+    /// The buffer to pass for `view`, copying its adjoint to the device if needed
+    unsafe fn buffer<T, L>(
+        &self,
+        queue: &Queue,
+        view: &TransposedView<T, L>,
+    ) -> Result<Buffer<T>, Error>
+    where
+        T: OclPrm + NeutralMul,
+        L: MatrixLayout,
+        for<'b> Omatcopy<'b, T, L>: RunOmatcopy,
+    {
+        if self.adjoint {
+            Ok(omatcopy_new(queue, view.matrix.h(), T::ONE)?.buffer)
+        } else {
+            Ok(view.matrix.buffer.clone())
+        }
+    }
+}
+
+/// A and B of `params` in the layout of C
+fn operands<T, LA, LB, LC>(params: &Gemm<T, LA, LB, LC>) -> (Operand, Operand)
+where
+    T: HostScalar + NeutralAdd + NeutralMul,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
+{
+    let layout = params.c.layout.layout();
+    (
+        Operand::map(&params.a, layout),
+        Operand::map(&params.b, layout),
+    )
+}
+
+/// The buffers to pass for A and B, mapped by [`operands`]
+unsafe fn operand_buffers<T, LA, LB, LC>(
+    params: &Gemm<T, LA, LB, LC>,
+    a: &Operand,
+    b: &Operand,
+) -> Result<(Buffer<T>, Buffer<T>), Error>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
+    for<'b> Omatcopy<'b, T, LA>: RunOmatcopy,
+    for<'b> Omatcopy<'b, T, LB>: RunOmatcopy,
+{
+    Ok((
+        a.buffer(params.queue, &params.a)?,
+        b.buffer(params.queue, &params.b)?,
+    ))
 }
+
 pub trait RunGemm {
     unsafe fn run(self) -> Result<(), Error>;
+
+    /// Size in bytes of the workspace clblast needs for this gemm, see `temp_buffer`
+    unsafe fn temp_buffer_size(&self) -> Result<usize, Error>;
 }
 
-impl<'a, L> RunGemm for Gemm<'a, f32, L>
+impl<'a, LA, LB, LC> RunGemm for Gemm<'a, f32, LA, LB, LC>
 where
-    L: MatrixLayout,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
 {
     unsafe fn run(self) -> Result<(), Error> {
-        let (k, n, m) = assert_dimensions(&self);
+        let (k, n, m) = check_dimensions(&self)?;
+        if self.temp_buffer.is_some() {
+            check_temp_buffer(&self, self.temp_buffer_size()?)?;
+        }
+        let (a, b) = operands(&self);
+        let (a_buffer, b_buffer) = operand_buffers(&self, &a, &b)?;
 
-        let res = CLBlastSgemm(
-            L::default().to_c(),
-            self.transpose_a.to_c(),
-            self.transpose_b.to_c(),
+        let res = CLBlastSgemmWithTempBuffer(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
             m as u64,
             n as u64,
             k as u64,
             self.alpha,
-            self.a.buffer.as_ptr(),
-            self.a.offset as u64,
-            self.a.stride as u64,
-            self.b.buffer.as_ptr(),
-            self.b.offset as u64,
-            self.b.stride as u64,
+            a_buffer.as_ptr(),
+            a.offset as u64,
+            a.stride as u64,
+            b_buffer.as_ptr(),
+            b.offset as u64,
+            b.stride as u64,
             self.beta,
             self.c.buffer.as_ptr(),
             self.c.offset as u64,
             self.c.stride as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
+            temp_buffer_ptr(&self),
         );
 
         Error::from_c_either(res)
     }
+
+    unsafe fn temp_buffer_size(&self) -> Result<usize, Error> {
+        let (k, n, m) = check_dimensions(self)?;
+        let (a, b) = operands(self);
+        let mut size = 0;
+
+        let res = CLBlastSGemmTempBufferSize(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            a.offset as u64,
+            a.stride as u64,
+            b.offset as u64,
+            b.stride as u64,
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut size,
+        );
+
+        Error::from_c_either(res)?;
+        Ok(size as usize)
+    }
 }
 
-impl<'a, L> RunGemm for Gemm<'a, f64, L>
+impl<'a, LA, LB, LC> RunGemm for Gemm<'a, f64, LA, LB, LC>
 where
-    L: MatrixLayout,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
 {
     unsafe fn run(self) -> Result<(), Error> {
-        let (k, n, m) = assert_dimensions(&self);
+        let (k, n, m) = check_dimensions(&self)?;
+        if self.temp_buffer.is_some() {
+            check_temp_buffer(&self, self.temp_buffer_size()?)?;
+        }
+        let (a, b) = operands(&self);
+        let (a_buffer, b_buffer) = operand_buffers(&self, &a, &b)?;
 
-        let res = CLBlastDgemm(
-            self.a.layout.to_c(),
-            self.transpose_a.to_c(),
-            self.transpose_b.to_c(),
+        let res = CLBlastDgemmWithTempBuffer(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
             m as u64,
             n as u64,
             k as u64,
             self.alpha,
-            self.a.buffer.as_ptr(),
-            self.a.offset as u64,
-            self.a.stride as u64,
-            self.b.buffer.as_ptr(),
-            self.b.offset as u64,
-            self.b.stride as u64,
+            a_buffer.as_ptr(),
+            a.offset as u64,
+            a.stride as u64,
+            b_buffer.as_ptr(),
+            b.offset as u64,
+            b.stride as u64,
             self.beta,
             self.c.buffer.as_ptr(),
             self.c.offset as u64,
             self.c.stride as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
+            temp_buffer_ptr(&self),
         );
 
         Error::from_c_either(res)
     }
+
+    unsafe fn temp_buffer_size(&self) -> Result<usize, Error> {
+        let (k, n, m) = check_dimensions(self)?;
+        let (a, b) = operands(self);
+        let mut size = 0;
+
+        let res = CLBlastDGemmTempBufferSize(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            a.offset as u64,
+            a.stride as u64,
+            b.offset as u64,
+            b.stride as u64,
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut size,
+        );
+
+        Error::from_c_either(res)?;
+        Ok(size as usize)
+    }
 }
 
-impl<'a, L> RunGemm for Gemm<'a, Complex32, L>
+impl<'a, LA, LB, LC> RunGemm for Gemm<'a, Complex32, LA, LB, LC>
 where
-    L: MatrixLayout,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
 {
     unsafe fn run(self) -> Result<(), Error> {
-        let (k, n, m) = assert_dimensions(&self);
+        let (k, n, m) = check_dimensions(&self)?;
+        if self.temp_buffer.is_some() {
+            check_temp_buffer(&self, self.temp_buffer_size()?)?;
+        }
+        let (a, b) = operands(&self);
+        let (a_buffer, b_buffer) = operand_buffers(&self, &a, &b)?;
         let alpha = cl_float2 {
             s: [self.alpha.re, self.alpha.im],
         };
         let alpha = cl_float2 {
             s: [self.alpha.re, self.alpha.im],
         };
-        let res = CLBlastCgemm(
-            self.a.layout.to_c(),
-            self.transpose_a.to_c(),
-            self.transpose_b.to_c(),
+        let res = CLBlastCgemmWithTempBuffer(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
             m as u64,
             n as u64,
             k as u64,
             self.alpha.to_c(),
-            self.a.buffer.as_ptr(),
-            self.a.offset as u64,
-            self.a.stride as u64,
-            self.b.buffer.as_ptr(),
-            self.b.offset as u64,
-            self.b.stride as u64,
+            a_buffer.as_ptr(),
+            a.offset as u64,
+            a.stride as u64,
+            b_buffer.as_ptr(),
+            b.offset as u64,
+            b.stride as u64,
             self.beta.to_c(),
             self.c.buffer.as_ptr(),
             self.c.offset as u64,
             self.c.stride as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
+            temp_buffer_ptr(&self),
         );
 
         Error::from_c_either(res)
     }
+
+    unsafe fn temp_buffer_size(&self) -> Result<usize, Error> {
+        let (k, n, m) = check_dimensions(self)?;
+        let (a, b) = operands(self);
+        let mut size = 0;
+
+        let res = CLBlastCGemmTempBufferSize(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            a.offset as u64,
+            a.stride as u64,
+            b.offset as u64,
+            b.stride as u64,
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut size,
+        );
+
+        Error::from_c_either(res)?;
+        Ok(size as usize)
+    }
 }
 
-impl<'a, L> RunGemm for Gemm<'a, Complex64, L>
+impl<'a, LA, LB, LC> RunGemm for Gemm<'a, Complex64, LA, LB, LC>
 where
-    L: MatrixLayout,
+    LA: MatrixLayout,
+    LB: MatrixLayout,
+    LC: MatrixLayout,
 {
     unsafe fn run(self) -> Result<(), Error> {
-        let (k, n, m) = assert_dimensions(&self);
+        let (k, n, m) = check_dimensions(&self)?;
+        if self.temp_buffer.is_some() {
+            check_temp_buffer(&self, self.temp_buffer_size()?)?;
+        }
+        let (a, b) = operands(&self);
+        let (a_buffer, b_buffer) = operand_buffers(&self, &a, &b)?;
 
-        let res = CLBlastZgemm(
-            self.a.layout.to_c(),
-            self.transpose_a.to_c(),
-            self.transpose_b.to_c(),
+        let res = CLBlastZgemmWithTempBuffer(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
             m as u64,
             n as u64,
             k as u64,
             self.alpha.to_c(),
-            self.a.buffer.as_ptr(),
-            self.a.offset as u64,
-            self.a.stride as u64,
-            self.b.buffer.as_ptr(),
-            self.b.offset as u64,
-            self.b.stride as u64,
+            a_buffer.as_ptr(),
+            a.offset as u64,
+            a.stride as u64,
+            b_buffer.as_ptr(),
+            b.offset as u64,
+            b.stride as u64,
             self.beta.to_c(),
             self.c.buffer.as_ptr(),
             self.c.offset as u64,
             self.c.stride as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
+            temp_buffer_ptr(&self),
         );
 
         Error::from_c_either(res)
     }
+
+    unsafe fn temp_buffer_size(&self) -> Result<usize, Error> {
+        let (k, n, m) = check_dimensions(self)?;
+        let (a, b) = operands(self);
+        let mut size = 0;
+
+        let res = CLBlastZGemmTempBufferSize(
+            self.c.layout.to_c(),
+            a.transpose.to_c(),
+            b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            a.offset as u64,
+            a.stride as u64,
+            b.offset as u64,
+            b.stride as u64,
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut size,
+        );
+
+        Error::from_c_either(res)?;
+        Ok(size as usize)
+    }
 }
 
 #[cfg(test)]
@@ -232,7 +551,8 @@ mod test {
     use rand::prelude::*;
     use rand_chacha::ChaCha20Rng;
 
-    use crate::LayoutRowMajor;
+    use crate::MatrixBuffer;
+    use crate::{DynLayout, LayoutColMajor, LayoutRowMajor};
 
     use super::*;
 
@@ -358,6 +678,205 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_dimensions_all_transpose_combinations() {
+        let (m, n, k) = (2, 3, 4);
+        let transposes = [
+            MatrixTranspose::No,
+            MatrixTranspose::Yes,
+            MatrixTranspose::Conjugate,
+        ];
+        for &transpose_a in transposes.iter() {
+            for &transpose_b in transposes.iter() {
+                // stored shapes, such that op(A) is m x k and op(B) is k x n
+                let a = transpose_a.apply(m, k);
+                let b = transpose_b.apply(k, n);
+                pretty::assert_eq!(
+                    gemm_dimensions("gemm", a, transpose_a, b, transpose_b, (m, n)).unwrap(),
+                    (k, n, m),
+                    "transpose_a: {:?}, transpose_b: {:?}",
+                    transpose_a,
+                    transpose_b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dimensions_transposed_mismatch() {
+        // A is 2 x 4, so op(A) = A^T is 4 x 2, which does not fit B (4 x 3)
+        let res = gemm_dimensions(
+            "gemm",
+            (2, 4),
+            MatrixTranspose::Yes,
+            (4, 3),
+            MatrixTranspose::No,
+            (4, 3),
+        );
+        assert!(matches!(
+            res,
+            Err(crate::Error::Dimension {
+                routine: "gemm",
+                operand: "op(b).rows (k)",
+                expected: 2,
+                actual: 4,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_transposed_view() {
+        let pro_que = ProQue::builder().src("").dims(21).build().unwrap();
+        // A is stored as 4 x 3, so A^T is 3 x 4
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 3, 4, 1.0f32, LayoutRowMajor);
+        let b_matrix = MatrixBuffer::new_default(&pro_que, 5, 4, 1.0f32, LayoutRowMajor);
+        let mut c_matrix = MatrixBuffer::new_default(&pro_que, 5, 3, 0.0f32, LayoutRowMajor);
+
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(a_matrix.t())
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        pretty::assert_eq!(read_buffer_to_matrix(c_matrix), vec![vec![4.0; 5]; 3]);
+    }
+
+    #[test]
+    fn test_dyn_layout() {
+        let pro_que = ProQue::builder().src("").dims(21).build().unwrap();
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 4, 3, 1.0f32, DynLayout::col_major());
+        let b_matrix = MatrixBuffer::new_default(&pro_que, 5, 4, 1.0f32, DynLayout::row_major());
+        let mut c_matrix =
+            MatrixBuffer::new_default(&pro_que, 5, 3, 0.0f32, DynLayout::col_major());
+
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut result = vec![0.0; 15];
+        c_matrix.buffer().read(&mut result).enq().unwrap();
+        pretty::assert_eq!(result, vec![4.0; 15]);
+    }
+
+    #[test]
+    fn test_mixed_layouts() {
+        let pro_que = ProQue::builder().src("").dims(6).build().unwrap();
+        // A = [[1, 2], [3, 4], [5, 6]], B = [[1, 0], [1, 1]]
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 2, 3, 0.0f32, LayoutRowMajor);
+        a_matrix
+            .buffer()
+            .write(&vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0][..])
+            .enq()
+            .unwrap();
+        let b_matrix = MatrixBuffer::new_default(&pro_que, 2, 2, 0.0f32, LayoutColMajor);
+        b_matrix
+            .buffer()
+            .write(&vec![1.0, 1.0, 0.0, 1.0][..])
+            .enq()
+            .unwrap();
+        let mut c_matrix = MatrixBuffer::new_default(&pro_que, 2, 3, 0.0f32, LayoutColMajor);
+
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        // C = [[3, 2], [7, 4], [11, 6]], column-major
+        let mut result = vec![0.0; 6];
+        c_matrix.buffer().read(&mut result).enq().unwrap();
+        pretty::assert_eq!(result, vec![3.0, 7.0, 11.0, 2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn test_mixed_layouts_adjoint() {
+        let pro_que = ProQue::builder().src("").dims(2).build().unwrap();
+        // A = [[i, 2]], so A^H = [[-i], [2]]
+        let a_matrix =
+            MatrixBuffer::new_default(&pro_que, 2, 1, Complex32::new(0.0, 0.0), LayoutRowMajor);
+        a_matrix
+            .buffer()
+            .write(&vec![Complex32::new(0.0, 1.0), Complex32::new(2.0, 0.0)][..])
+            .enq()
+            .unwrap();
+        let b_matrix =
+            MatrixBuffer::new_default(&pro_que, 1, 1, Complex32::new(1.0, 0.0), LayoutColMajor);
+        let mut c_matrix =
+            MatrixBuffer::new_default(&pro_que, 1, 2, Complex32::new(0.0, 0.0), LayoutColMajor);
+
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(a_matrix.h())
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut result = vec![Complex32::new(0.0, 0.0); 2];
+        c_matrix.buffer().read(&mut result).enq().unwrap();
+        pretty::assert_eq!(
+            result,
+            vec![Complex32::new(0.0, -1.0), Complex32::new(2.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_real_adjoint_is_transpose() {
+        let pro_que = ProQue::builder().src("").dims(6).build().unwrap();
+        let real = MatrixBuffer::new_default(&pro_que, 3, 2, 1.0f32, LayoutRowMajor);
+        let complex =
+            MatrixBuffer::new_default(&pro_que, 3, 2, Complex32::new(1.0, 0.0), LayoutRowMajor);
+
+        // in the other layout A^H of a real A is read as A, without copying its adjoint
+        let operand = Operand::map(&real.h(), Layout::ColMajor);
+        assert_eq!(operand.transpose, MatrixTranspose::No);
+        assert!(!operand.adjoint);
+        assert!(Operand::map(&complex.h(), Layout::ColMajor).adjoint);
+    }
+
+    #[test]
+    fn test_user_temp_buffer() {
+        let pro_que = ProQue::builder().src("").dims(21).build().unwrap();
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 4, 3, 1.0f32, LayoutRowMajor);
+        let b_matrix = MatrixBuffer::new_default(&pro_que, 5, 4, 1.0f32, LayoutRowMajor);
+        let mut c_matrix = MatrixBuffer::new_default(&pro_que, 5, 3, 0.0f32, LayoutRowMajor);
+
+        let size = unsafe {
+            Gemm::builder()
+                .queue(&pro_que.queue())
+                .a(&a_matrix)
+                .b(&b_matrix)
+                .c(&mut c_matrix)
+                .build()
+                .temp_buffer_size()
+                .unwrap()
+        };
+        let temp_buffer = pro_que
+            .buffer_builder::<f32>()
+            .len(size / std::mem::size_of::<f32>() + 1)
+            .build()
+            .unwrap();
+
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .temp_buffer(&temp_buffer)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        pretty::assert_eq!(read_buffer_to_matrix(c_matrix), vec![vec![4.0; 5]; 3]);
+    }
+
     #[test]
     fn test_reference_1_3() {
         let res = reference_implementation(3, 1, &vec![1.0, 2.0, 3.0], &vec![1.0]);
@@ -0,0 +1,383 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::OclPrm;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastCgemmBatched;
+use clblast_sys::CLBlastDgemmBatched;
+use clblast_sys::CLBlastSgemmBatched;
+use clblast_sys::CLBlastZgemmBatched;
+
+use crate::gemm::gemm_dimensions;
+use crate::Error;
+use crate::MatrixBuffer;
+use crate::MatrixLayout;
+use crate::MatrixViewMut;
+use crate::ReprSys;
+use crate::TransposedView;
+
+/// Batched version of gemm: Computes `C[i] := alphas[i] * A[i] * B[i] + betas[i] * C[i]` for every batch `i`
+/// in a single call.
+///
+/// `a`, `b` and `c` describe the shape and stride shared by all batches, as well as the buffers all batches
+/// are stored in. Batch `i` starts `offsets[i].0` (`.1`, `.2`) values after the offset of `a` (`b`, `c`).
+///
+/// # Arguments
+/// - Matrix A: K⨯M (K Wide, M High)
+/// - Matrix B: N⨯K (N Wide, K High)
+/// - Matrix C: M⨯N (N Wide, M High)
+///
+/// A and B can be passed transposed with `.a(a.t())` or conjugate transposed with `.a(a.h())`,
+/// the shapes above then refer to the transposed matrices.
+#[derive(TypedBuilder)]
+pub struct GemmBatched<'a, T, L>
+where
+    T: OclPrm,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    #[builder(setter(into))]
+    a: TransposedView<'a, T, L>,
+    #[builder(setter(into))]
+    b: TransposedView<'a, T, L>,
+    #[builder(setter(into))]
+    c: MatrixViewMut<'a, T, L>,
+
+    /// Offsets of (A, B, C) of every batch
+    offsets: &'a [(usize, usize, usize)],
+
+    // factors, one per batch
+    alphas: &'a [T],
+    betas: &'a [T],
+}
+
+/// Offsets of A, B and C of every batch, as passed to clblast
+struct BatchOffsets {
+    a: Vec<u64>,
+    b: Vec<u64>,
+    c: Vec<u64>,
+}
+
+fn check_dimensions<T: OclPrm, L: MatrixLayout>(
+    params: &GemmBatched<T, L>,
+) -> Result<((usize, usize, usize), BatchOffsets), Error> {
+    let batch_count = params.offsets.len();
+    Error::check_min_dimension(
+        "gemm_batched",
+        "offsets.len() (batch_count)",
+        1,
+        batch_count,
+    )?;
+    Error::check_dimension(
+        "gemm_batched",
+        "alphas.len()",
+        batch_count,
+        params.alphas.len(),
+    )?;
+    Error::check_dimension(
+        "gemm_batched",
+        "betas.len()",
+        batch_count,
+        params.betas.len(),
+    )?;
+
+    let layout = params.c.layout.layout();
+    Error::check_layout(
+        "gemm_batched",
+        "a.layout",
+        layout,
+        params.a.matrix.layout.layout(),
+    )?;
+    Error::check_layout(
+        "gemm_batched",
+        "b.layout",
+        layout,
+        params.b.matrix.layout.layout(),
+    )?;
+
+    let (k, n, m) = gemm_dimensions(
+        "gemm_batched",
+        (params.a.matrix.rows, params.a.matrix.columns),
+        params.a.transpose,
+        (params.b.matrix.rows, params.b.matrix.columns),
+        params.b.transpose,
+        (params.c.rows, params.c.columns),
+    )?;
+
+    let offsets = BatchOffsets {
+        a: batch_offsets(
+            params.a.matrix,
+            params.offsets.iter().map(|o| o.0),
+            "a buffer",
+        )?,
+        b: batch_offsets(
+            params.b.matrix,
+            params.offsets.iter().map(|o| o.1),
+            "b buffer",
+        )?,
+        c: batch_offsets(&params.c, params.offsets.iter().map(|o| o.2), "c buffer")?,
+    };
+
+    Ok(((k, n, m), offsets))
+}
+
+fn batch_offsets<T: OclPrm, L: MatrixLayout>(
+    matrix: &MatrixBuffer<T, L>,
+    offsets: impl Iterator<Item = usize>,
+    operand: &'static str,
+) -> Result<Vec<u64>, Error> {
+    let overflow = || Error::Overflow {
+        routine: "gemm_batched",
+        operand,
+    };
+    let extent = matrix.checked_extent().ok_or_else(overflow)?;
+    offsets
+        .map(|offset| {
+            let start = matrix.offset.checked_add(offset).ok_or_else(overflow)?;
+            let end = start.checked_add(extent).ok_or_else(overflow)?;
+            Error::check_min_dimension("gemm_batched", operand, end, matrix.buffer.len())?;
+            Ok(start as u64)
+        })
+        .collect()
+}
+
+pub trait RunGemmBatched {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunGemmBatched for GemmBatched<'a, f32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let ((k, n, m), offsets) = check_dimensions(&self)?;
+
+        let res = CLBlastSgemmBatched(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            self.alphas.as_ptr(),
+            self.a.matrix.buffer.as_ptr(),
+            offsets.a.as_ptr(),
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            offsets.b.as_ptr(),
+            self.b.matrix.stride as u64,
+            self.betas.as_ptr(),
+            self.c.buffer.as_ptr(),
+            offsets.c.as_ptr(),
+            self.c.stride as u64,
+            self.offsets.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunGemmBatched for GemmBatched<'a, f64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let ((k, n, m), offsets) = check_dimensions(&self)?;
+
+        let res = CLBlastDgemmBatched(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            self.alphas.as_ptr(),
+            self.a.matrix.buffer.as_ptr(),
+            offsets.a.as_ptr(),
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            offsets.b.as_ptr(),
+            self.b.matrix.stride as u64,
+            self.betas.as_ptr(),
+            self.c.buffer.as_ptr(),
+            offsets.c.as_ptr(),
+            self.c.stride as u64,
+            self.offsets.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunGemmBatched for GemmBatched<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let ((k, n, m), offsets) = check_dimensions(&self)?;
+        let alphas = self.alphas.iter().map(|a| a.to_c()).collect::<Vec<_>>();
+        let betas = self.betas.iter().map(|b| b.to_c()).collect::<Vec<_>>();
+
+        let res = CLBlastCgemmBatched(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            alphas.as_ptr(),
+            self.a.matrix.buffer.as_ptr(),
+            offsets.a.as_ptr(),
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            offsets.b.as_ptr(),
+            self.b.matrix.stride as u64,
+            betas.as_ptr(),
+            self.c.buffer.as_ptr(),
+            offsets.c.as_ptr(),
+            self.c.stride as u64,
+            self.offsets.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunGemmBatched for GemmBatched<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let ((k, n, m), offsets) = check_dimensions(&self)?;
+        let alphas = self.alphas.iter().map(|a| a.to_c()).collect::<Vec<_>>();
+        let betas = self.betas.iter().map(|b| b.to_c()).collect::<Vec<_>>();
+
+        let res = CLBlastZgemmBatched(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            alphas.as_ptr(),
+            self.a.matrix.buffer.as_ptr(),
+            offsets.a.as_ptr(),
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            offsets.b.as_ptr(),
+            self.b.matrix.stride as u64,
+            betas.as_ptr(),
+            self.c.buffer.as_ptr(),
+            offsets.c.as_ptr(),
+            self.c.stride as u64,
+            self.offsets.len() as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+
+    use super::*;
+
+    #[test]
+    fn test_float() {
+        let pro_que = ProQue::builder().src("").dims(2 * 4).build().unwrap();
+        // two batches of 2x2 matrices stored back to back
+        let a_matrix = MatrixBuffer::new(
+            2,
+            2,
+            pro_que.buffer_builder().fill_val(1.0f32).build().unwrap(),
+            LayoutRowMajor,
+        );
+        let b_matrix = MatrixBuffer::new(
+            2,
+            2,
+            pro_que.buffer_builder().fill_val(2.0f32).build().unwrap(),
+            LayoutRowMajor,
+        );
+        let mut c_matrix = MatrixBuffer::new(
+            2,
+            2,
+            pro_que.buffer_builder().fill_val(1.0f32).build().unwrap(),
+            LayoutRowMajor,
+        );
+
+        let task = GemmBatched::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .offsets(&[(0, 0, 0), (4, 4, 4)])
+            .alphas(&[1.0, 2.0])
+            .betas(&[0.0, 1.0])
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![0.0; 8];
+        c_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        assert_eq!(out, vec![4.0, 4.0, 4.0, 4.0, 9.0, 9.0, 9.0, 9.0]);
+    }
+
+    #[test]
+    fn test_offset_out_of_range() {
+        let pro_que = ProQue::builder().src("").dims(2 * 4).build().unwrap();
+        let a_matrix = MatrixBuffer::new(
+            2,
+            2,
+            pro_que.create_buffer::<f32>().unwrap(),
+            LayoutRowMajor,
+        );
+        let b_matrix = MatrixBuffer::new(
+            2,
+            2,
+            pro_que.create_buffer::<f32>().unwrap(),
+            LayoutRowMajor,
+        );
+        let mut c_matrix = MatrixBuffer::new(
+            2,
+            2,
+            pro_que.create_buffer::<f32>().unwrap(),
+            LayoutRowMajor,
+        );
+
+        let task = GemmBatched::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .offsets(&[(0, 0, 0), (5, 4, 4)])
+            .alphas(&[1.0, 1.0])
+            .betas(&[0.0, 0.0])
+            .build();
+        let res = unsafe { task.run() };
+        assert!(matches!(
+            res,
+            Err(Error::Dimension {
+                operand: "a buffer",
+                expected: 9,
+                actual: 8,
+                ..
+            })
+        ));
+    }
+}
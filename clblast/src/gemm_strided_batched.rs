@@ -0,0 +1,323 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::OclPrm;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastCgemmStridedBatched;
+use clblast_sys::CLBlastDgemmStridedBatched;
+use clblast_sys::CLBlastSgemmStridedBatched;
+use clblast_sys::CLBlastZgemmStridedBatched;
+
+use crate::gemm::gemm_dimensions;
+use crate::BatchedMatrixBuffer;
+use crate::Error;
+use crate::MatrixLayout;
+use crate::NeutralAdd;
+use crate::NeutralMul;
+use crate::ReprSys;
+use crate::TransposedBatch;
+
+/// Strided batched version of gemm: Computes `C[i] := alpha * A[i] * B[i] + beta * C[i]` for every matrix `i`
+/// of the batches in a single call.
+///
+/// # Arguments
+/// - Matrices A: K⨯M (K Wide, M High)
+/// - Matrices B: N⨯K (N Wide, K High)
+/// - Matrices C: M⨯N (N Wide, M High)
+///
+/// A and B can be passed transposed with `.a(a.t())` or conjugate transposed with `.a(a.h())`,
+/// the shapes above then refer to the transposed matrices.
+#[derive(TypedBuilder)]
+pub struct GemmStridedBatched<'a, T, L>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    #[builder(setter(into))]
+    a: TransposedBatch<'a, T, L>,
+    #[builder(setter(into))]
+    b: TransposedBatch<'a, T, L>,
+    c: &'a mut BatchedMatrixBuffer<T, L>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+    #[builder(default=NeutralAdd::ZERO)]
+    beta: T,
+}
+
+fn check_dimensions<T: OclPrm + NeutralAdd + NeutralMul, L: MatrixLayout>(
+    params: &GemmStridedBatched<T, L>,
+) -> Result<(usize, usize, usize), Error> {
+    let routine = "gemm_strided_batched";
+    Error::check_min_dimension(routine, "c.batch_count", 1, params.c.batch_count)?;
+    Error::check_dimension(
+        routine,
+        "a.batch_count",
+        params.c.batch_count,
+        params.a.batch.batch_count,
+    )?;
+    Error::check_dimension(
+        routine,
+        "b.batch_count",
+        params.c.batch_count,
+        params.b.batch.batch_count,
+    )?;
+
+    let layout = params.c.layout.layout();
+    Error::check_layout(routine, "a.layout", layout, params.a.batch.layout.layout())?;
+    Error::check_layout(routine, "b.layout", layout, params.b.batch.layout.layout())?;
+
+    let (k, n, m) = gemm_dimensions(
+        routine,
+        (params.a.batch.rows, params.a.batch.columns),
+        params.a.transpose,
+        (params.b.batch.rows, params.b.batch.columns),
+        params.b.transpose,
+        (params.c.rows, params.c.columns),
+    )?;
+
+    // A and B are only read, so their matrices may overlap (e.g. a `batch_stride` of 0)
+    check_batch_end(routine, "a buffer", params.a.batch)?;
+    check_batch_end(routine, "b buffer", params.b.batch)?;
+    // the matrices of C are written concurrently and must not overlap
+    params.c.validate()?;
+
+    Ok((k, n, m))
+}
+
+fn check_batch_end<T: OclPrm, L: MatrixLayout>(
+    routine: &'static str,
+    operand: &'static str,
+    batch: &BatchedMatrixBuffer<T, L>,
+) -> Result<(), Error> {
+    let end = batch
+        .checked_extent()
+        .and_then(|extent| extent.checked_add(batch.offset))
+        .ok_or(Error::Overflow { routine, operand })?;
+    Error::check_min_dimension(routine, operand, end, batch.buffer.len())
+}
+
+pub trait RunGemmStridedBatched {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunGemmStridedBatched for GemmStridedBatched<'a, f32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (k, n, m) = check_dimensions(&self)?;
+
+        let res = CLBlastSgemmStridedBatched(
+            self.a.batch.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            self.alpha,
+            self.a.batch.buffer.as_ptr(),
+            self.a.batch.offset as u64,
+            self.a.batch.stride as u64,
+            self.a.batch.batch_stride as u64,
+            self.b.batch.buffer.as_ptr(),
+            self.b.batch.offset as u64,
+            self.b.batch.stride as u64,
+            self.b.batch.batch_stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            self.c.batch_stride as u64,
+            self.c.batch_count as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunGemmStridedBatched for GemmStridedBatched<'a, f64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (k, n, m) = check_dimensions(&self)?;
+
+        let res = CLBlastDgemmStridedBatched(
+            self.a.batch.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            self.alpha,
+            self.a.batch.buffer.as_ptr(),
+            self.a.batch.offset as u64,
+            self.a.batch.stride as u64,
+            self.a.batch.batch_stride as u64,
+            self.b.batch.buffer.as_ptr(),
+            self.b.batch.offset as u64,
+            self.b.batch.stride as u64,
+            self.b.batch.batch_stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            self.c.batch_stride as u64,
+            self.c.batch_count as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunGemmStridedBatched for GemmStridedBatched<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (k, n, m) = check_dimensions(&self)?;
+
+        let res = CLBlastCgemmStridedBatched(
+            self.a.batch.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            self.alpha.to_c(),
+            self.a.batch.buffer.as_ptr(),
+            self.a.batch.offset as u64,
+            self.a.batch.stride as u64,
+            self.a.batch.batch_stride as u64,
+            self.b.batch.buffer.as_ptr(),
+            self.b.batch.offset as u64,
+            self.b.batch.stride as u64,
+            self.b.batch.batch_stride as u64,
+            self.beta.to_c(),
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            self.c.batch_stride as u64,
+            self.c.batch_count as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunGemmStridedBatched for GemmStridedBatched<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (k, n, m) = check_dimensions(&self)?;
+
+        let res = CLBlastZgemmStridedBatched(
+            self.a.batch.layout.to_c(),
+            self.a.transpose.to_c(),
+            self.b.transpose.to_c(),
+            m as u64,
+            n as u64,
+            k as u64,
+            self.alpha.to_c(),
+            self.a.batch.buffer.as_ptr(),
+            self.a.batch.offset as u64,
+            self.a.batch.stride as u64,
+            self.a.batch.batch_stride as u64,
+            self.b.batch.buffer.as_ptr(),
+            self.b.batch.offset as u64,
+            self.b.batch.stride as u64,
+            self.b.batch.batch_stride as u64,
+            self.beta.to_c(),
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            self.c.batch_stride as u64,
+            self.c.batch_count as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+
+    use super::*;
+
+    #[test]
+    fn test_float() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix = BatchedMatrixBuffer::new_default(&pro_que, 3, 2, 4, 1.0f32, LayoutRowMajor);
+        let b_matrix = BatchedMatrixBuffer::new_default(&pro_que, 2, 3, 4, 2.0f32, LayoutRowMajor);
+        let mut c_matrix =
+            BatchedMatrixBuffer::new_default(&pro_que, 2, 2, 4, 0.0f32, LayoutRowMajor);
+
+        let task = GemmStridedBatched::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let last = c_matrix.batch(3);
+        let mut out = vec![0.0; last.size()];
+        last.buffer().read(&mut out[..]).offset(12).enq().unwrap();
+        assert_eq!(out, vec![6.0; 4]);
+    }
+
+    #[test]
+    fn test_overlapping_c() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        // A is broadcast to all batches, which is fine as it is only read
+        let a_matrix = BatchedMatrixBuffer::builder()
+            .buffer(pro_que.create_buffer::<f32>().unwrap())
+            .rows(2)
+            .columns(3)
+            .batch_count(4)
+            .batch_stride(0)
+            .layout(LayoutRowMajor)
+            .build();
+        let b_matrix = BatchedMatrixBuffer::new_default(&pro_que, 2, 3, 4, 2.0f32, LayoutRowMajor);
+        let mut c_matrix = BatchedMatrixBuffer::builder()
+            .buffer(pro_que.create_buffer::<f32>().unwrap())
+            .rows(2)
+            .columns(2)
+            .batch_count(4)
+            .batch_stride(2)
+            .layout(LayoutRowMajor)
+            .build();
+
+        let task = GemmStridedBatched::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(&mut c_matrix)
+            .build();
+        match unsafe { task.run() } {
+            Err(Error::Dimension { operand, .. }) => assert_eq!(operand, "batch_stride"),
+            _ => panic!("overlapping matrices of C must be rejected"),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::ptr;
+
+use num_complex::{Complex32, Complex64};
+use ocl::{OclPrm, Queue};
+
+use crate::{
+    Error, MatrixBuffer, MatrixLayout, MatrixViewMut, NeutralAdd, NeutralMul, ReprSys,
+    VectorBuffer, VectorViewMut,
+};
+
+use typed_builder::TypedBuilder;
+
+use clblast_sys::{CLBlastChad, CLBlastDhad, CLBlastShad, CLBlastZhad};
+
+/// Performs the element-wise (Hadamard) product `z = alpha * x ⊙ y + beta * z`, in which `x`, `y` and `z` are vectors
+/// and `alpha` and `beta` are scalar constants.
+#[derive(TypedBuilder)]
+pub struct Hadamard<'a, T: OclPrm + NeutralAdd + NeutralMul> {
+    /// OpenCL command queue associated with a context and device to execute the routine on.
+    queue: &'a Queue,
+
+    ///  Input scalar constant `alpha`
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+    ///  Input scalar constant `beta`
+    #[builder(default=NeutralAdd::ZERO)]
+    beta: T,
+
+    // OpenCl buffer containing the x vector
+    x_vector: &'a VectorBuffer<T>,
+    // OpenCl buffer containing the y vector
+    y_vector: &'a VectorBuffer<T>,
+    // OpenCl buffer to store the output z vector
+    #[builder(setter(into))]
+    z_vector: VectorViewMut<'a, T>,
+}
+
+pub trait RunHadamard {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+fn check_dimensions<'a, T: OclPrm + NeutralAdd + NeutralMul>(
+    params: &Hadamard<'a, T>,
+) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    Error::check_dimension("had", "y.len (n)", n, params.y_vector.len)?;
+    Error::check_dimension("had", "z.len (n)", n, params.z_vector.len)?;
+    params.x_vector.check_extent("had", "x buffer")?;
+    params.y_vector.check_extent("had", "y buffer")?;
+    params.z_vector.check_extent("had", "z buffer")?;
+    Ok(n)
+}
+
+impl<'a> RunHadamard for Hadamard<'a, f32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let n = check_dimensions(&self)?;
+
+        let res = CLBlastShad(
+            n as u64,
+            self.alpha,
+            self.x_vector.buffer.as_ptr(),
+            self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
+            self.y_vector.buffer.as_ptr(),
+            self.y_vector.offset as u64,
+            self.y_vector.inc as u64,
+            self.beta,
+            self.z_vector.buffer.as_ptr(),
+            self.z_vector.offset as u64,
+            self.z_vector.inc as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunHadamard for Hadamard<'a, f64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let n = check_dimensions(&self)?;
+
+        let res = CLBlastDhad(
+            n as u64,
+            self.alpha,
+            self.x_vector.buffer.as_ptr(),
+            self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
+            self.y_vector.buffer.as_ptr(),
+            self.y_vector.offset as u64,
+            self.y_vector.inc as u64,
+            self.beta,
+            self.z_vector.buffer.as_ptr(),
+            self.z_vector.offset as u64,
+            self.z_vector.inc as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunHadamard for Hadamard<'a, Complex32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let n = check_dimensions(&self)?;
+
+        let res = CLBlastChad(
+            n as u64,
+            self.alpha.to_c(),
+            self.x_vector.buffer.as_ptr(),
+            self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
+            self.y_vector.buffer.as_ptr(),
+            self.y_vector.offset as u64,
+            self.y_vector.inc as u64,
+            self.beta.to_c(),
+            self.z_vector.buffer.as_ptr(),
+            self.z_vector.offset as u64,
+            self.z_vector.inc as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunHadamard for Hadamard<'a, Complex64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        let n = check_dimensions(&self)?;
+
+        let res = CLBlastZhad(
+            n as u64,
+            self.alpha.to_c(),
+            self.x_vector.buffer.as_ptr(),
+            self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
+            self.y_vector.buffer.as_ptr(),
+            self.y_vector.offset as u64,
+            self.y_vector.inc as u64,
+            self.beta.to_c(),
+            self.z_vector.buffer.as_ptr(),
+            self.z_vector.offset as u64,
+            self.z_vector.inc as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+/// Element-wise (Hadamard) product of matrices: `Z = alpha * X ⊙ Y + beta * Z`
+///
+/// All matrices must have the same shape and be stored contiguously (i.e. with the default stride),
+/// so they can be treated as vectors of length `rows * columns`.
+#[derive(TypedBuilder)]
+pub struct MatrixHadamard<'a, T, L>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    x: &'a MatrixBuffer<T, L>,
+    y: &'a MatrixBuffer<T, L>,
+    #[builder(setter(into))]
+    z: MatrixViewMut<'a, T, L>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+    #[builder(default=NeutralAdd::ZERO)]
+    beta: T,
+}
+
+/// Only contiguous matrices can be treated as a single vector
+fn check_contiguous<T: OclPrm, L: MatrixLayout>(
+    matrix: &MatrixBuffer<T, L>,
+    operand: &'static str,
+) -> Result<(), Error> {
+    Error::check_dimension(
+        "had",
+        operand,
+        matrix.layout.default_stride(matrix.columns, matrix.rows),
+        matrix.stride,
+    )
+}
+
+fn check_matrix_dimensions<T, L>(params: &MatrixHadamard<T, L>) -> Result<(), Error>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    L: MatrixLayout,
+{
+    let layout = params.z.layout.layout();
+    Error::check_layout("had", "x.layout", layout, params.x.layout.layout())?;
+    Error::check_layout("had", "y.layout", layout, params.y.layout.layout())?;
+    Error::check_dimension("had", "x.rows", params.z.rows, params.x.rows)?;
+    Error::check_dimension("had", "x.columns", params.z.columns, params.x.columns)?;
+    Error::check_dimension("had", "y.rows", params.z.rows, params.y.rows)?;
+    Error::check_dimension("had", "y.columns", params.z.columns, params.y.columns)?;
+
+    check_contiguous(params.x, "x.stride")?;
+    check_contiguous(params.y, "y.stride")?;
+    check_contiguous(&params.z, "z.stride")
+}
+
+impl<'a, T, L> RunHadamard for MatrixHadamard<'a, T, L>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    L: MatrixLayout,
+    for<'b> Hadamard<'b, T>: RunHadamard,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        check_matrix_dimensions(&self)?;
+
+        let x_vector = VectorBuffer::builder()
+            .buffer(self.x.buffer.clone())
+            .offset(self.x.offset)
+            .len(self.x.size())
+            .build();
+        let y_vector = VectorBuffer::builder()
+            .buffer(self.y.buffer.clone())
+            .offset(self.y.offset)
+            .len(self.y.size())
+            .build();
+        let mut z_vector = VectorBuffer::builder()
+            .buffer(self.z.buffer.clone())
+            .offset(self.z.offset)
+            .len(self.z.size())
+            .build();
+
+        Hadamard::builder()
+            .queue(self.queue)
+            .alpha(self.alpha)
+            .beta(self.beta)
+            .x_vector(&x_vector)
+            .y_vector(&y_vector)
+            .z_vector(&mut z_vector)
+            .build()
+            .run()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LayoutRowMajor;
+    use crate::VectorBuffer;
+
+    #[test]
+    fn test_float() {
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let x_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let y_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let z_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let x_vector = VectorBuffer::builder().buffer(x_buffer).build();
+        let y_vector = VectorBuffer::builder().buffer(y_buffer).build();
+        let mut z_vector = VectorBuffer::builder().buffer(z_buffer).build();
+        let task = Hadamard::builder()
+            .queue(&pro_que.queue())
+            .x_vector(&x_vector)
+            .y_vector(&y_vector)
+            .z_vector(&mut z_vector)
+            .build();
+        unsafe { task.run().unwrap() }
+    }
+
+    #[test]
+    fn test_matrix_float() {
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let x_matrix = MatrixBuffer::new_default(&pro_que, 3, 2, 2.0f32, LayoutRowMajor);
+        let y_matrix = MatrixBuffer::new_default(&pro_que, 3, 2, 3.0f32, LayoutRowMajor);
+        let mut z_matrix = MatrixBuffer::new_default(&pro_que, 3, 2, 1.0f32, LayoutRowMajor);
+        let task = MatrixHadamard::builder()
+            .queue(&pro_que.queue())
+            .x(&x_matrix)
+            .y(&y_matrix)
+            .z(&mut z_matrix)
+            .beta(1.0)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![0.0; z_matrix.size()];
+        z_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        assert_eq!(out, vec![7.0; 6]);
+    }
+}
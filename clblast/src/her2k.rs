@@ -0,0 +1,196 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastCher2k;
+use clblast_sys::CLBlastZher2k;
+
+use crate::ComplexPrm;
+use crate::Error;
+use crate::Hermitian;
+use crate::MatrixLayout;
+use crate::MatrixTranspose;
+use crate::MatrixViewMut;
+use crate::NeutralAdd;
+use crate::NeutralMul;
+use crate::ReprSys;
+use crate::TransposedView;
+
+/// Hermitian rank-2K update: Computes `C := alpha * A * B^H + conj(alpha) * B * A^H + beta * C`
+/// or `C := alpha * A^H * B + conj(alpha) * B^H * A + beta * C`
+///
+/// `alpha` is complex, while `beta` is real. Only the triangle recorded in the [`Hermitian`] wrapper of `C` is read and updated.
+///
+/// # Arguments
+/// - Matrix A: K⨯N (K Wide, N High)
+/// - Matrix B: same shape as A
+/// - Matrix C: N⨯N (Hermitian)
+///
+/// A and B can be passed conjugate transposed with `.a(a.h()).b(b.h())`, the shapes above then refer to
+/// `A^H` and `B^H`. CLBlast applies one transpose flag to both, and rejects a plain transpose.
+#[derive(TypedBuilder)]
+pub struct Her2k<'a, T, L>
+where
+    T: ComplexPrm + NeutralAdd + NeutralMul,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    #[builder(setter(into))]
+    a: TransposedView<'a, T, L>,
+    #[builder(setter(into))]
+    b: TransposedView<'a, T, L>,
+    /// e.g. `.c(Hermitian::upper(&mut c))`, only the recorded triangle is updated
+    #[builder(setter(into))]
+    c: Hermitian<MatrixViewMut<'a, T, L>>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+    #[builder(default=NeutralAdd::ZERO)]
+    beta: T::Real,
+}
+
+fn check_dimensions<T: ComplexPrm + NeutralAdd + NeutralMul, L: MatrixLayout>(
+    params: &Her2k<T, L>,
+) -> Result<(usize, usize), Error> {
+    // Performs the matrix product C = alpha * A * B^H + conj(alpha) * B * A^H + beta * C or
+    // C = alpha * A^H * B + conj(alpha) * B^H * A + beta * C, in which A and B (n rows by k columns) are two
+    // general matrices (transposed in the second case), C (n rows by n columns) is the Hermitian
+    // matrix to be updated, alpha is a complex and beta a real scalar value.
+
+    if params.a.transpose == MatrixTranspose::Yes {
+        return Err(Error::UnsupportedTranspose {
+            routine: "her2k",
+            operand: "a",
+            transpose: MatrixTranspose::Yes,
+        });
+    }
+    // CLBlast takes a single transpose flag for A and B
+    if params.b.transpose != params.a.transpose {
+        return Err(Error::UnsupportedTranspose {
+            routine: "her2k",
+            operand: "b",
+            transpose: params.b.transpose,
+        });
+    }
+    let (n, k) = (params.a.rows(), params.a.columns());
+
+    let layout = params.c.layout.layout();
+    Error::check_layout("her2k", "a.layout", layout, params.a.matrix.layout.layout())?;
+    Error::check_layout("her2k", "b.layout", layout, params.b.matrix.layout.layout())?;
+    Error::check_dimension("her2k", "b.rows", n, params.b.rows())?;
+    Error::check_dimension("her2k", "b.columns", k, params.b.columns())?;
+    Error::check_dimension("her2k", "c.rows (n)", n, params.c.rows)?;
+    Error::check_dimension("her2k", "c.columns (n)", n, params.c.columns)?;
+
+    Ok((n, k))
+}
+
+pub trait RunHer2k {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunHer2k for Her2k<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastCher2k(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            self.b.matrix.offset as u64,
+            self.b.matrix.stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunHer2k for Her2k<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastZher2k(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            self.b.matrix.offset as u64,
+            self.b.matrix.stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+    use crate::MatrixBuffer;
+
+    use super::*;
+
+    #[test]
+    fn test_complex32() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix =
+            MatrixBuffer::new_default(&pro_que, 3, 4, Complex32::new(1.0, 0.0), LayoutRowMajor);
+        let b_matrix =
+            MatrixBuffer::new_default(&pro_que, 3, 4, Complex32::new(0.0, 1.0), LayoutRowMajor);
+        let mut c_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 4, Complex32::new(0.0, 0.0), LayoutRowMajor);
+
+        let task = Her2k::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(Hermitian::upper(&mut c_matrix))
+            .alpha(Complex32::new(0.0, 1.0))
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![Complex32::new(0.0, 0.0); c_matrix.size()];
+        c_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        // i * 1 * conj(i) + conj(i) * i * 1 = 2, summed over k = 3
+        assert_eq!(out[0], Complex32::new(6.0, 0.0));
+    }
+}
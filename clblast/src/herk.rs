@@ -0,0 +1,208 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastCherk;
+use clblast_sys::CLBlastZherk;
+
+use crate::ComplexPrm;
+use crate::Error;
+use crate::Hermitian;
+use crate::MatrixLayout;
+use crate::MatrixTranspose;
+use crate::MatrixViewMut;
+use crate::NeutralAdd;
+use crate::NeutralMul;
+use crate::ReprSys;
+use crate::TransposedView;
+
+/// Hermitian rank-K update: Computes `C := alpha * A * A^H + beta * C` or `C := alpha * A^H * A + beta * C`
+///
+/// `alpha` and `beta` are real, while `A` and `C` are complex. Only the triangle recorded in the
+/// [`Hermitian`] wrapper of `C` is read and updated.
+///
+/// # Arguments
+/// - Matrix A: K⨯N (K Wide, N High)
+/// - Matrix C: N⨯N (Hermitian)
+///
+/// A can be passed conjugate transposed with `.a(a.h())`, the shape above then refers to `A^H`
+/// and `C := alpha * A^H * A + beta * C` is computed. CLBlast rejects a plain transpose `.a(a.t())`.
+#[derive(TypedBuilder)]
+pub struct Herk<'a, T, L>
+where
+    T: ComplexPrm,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    #[builder(setter(into))]
+    a: TransposedView<'a, T, L>,
+    /// e.g. `.c(Hermitian::upper(&mut c))`, only the recorded triangle is updated
+    #[builder(setter(into))]
+    c: Hermitian<MatrixViewMut<'a, T, L>>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T::Real,
+    #[builder(default=NeutralAdd::ZERO)]
+    beta: T::Real,
+}
+
+fn check_dimensions<T: ComplexPrm, L: MatrixLayout>(
+    params: &Herk<T, L>,
+) -> Result<(usize, usize), Error> {
+    // Performs the matrix product C = alpha * A * A^H + beta * C or C = alpha * A^H * A + beta * C,
+    // in which A (n rows by k columns) is the input matrix (transposed in the second case),
+    // C (n rows by n columns) is the Hermitian matrix to be updated, and alpha and beta are real scalar values.
+
+    if params.a.transpose == MatrixTranspose::Yes {
+        return Err(Error::UnsupportedTranspose {
+            routine: "herk",
+            operand: "a",
+            transpose: MatrixTranspose::Yes,
+        });
+    }
+    let (n, k) = (params.a.rows(), params.a.columns());
+
+    Error::check_layout(
+        "herk",
+        "a.layout",
+        params.c.layout.layout(),
+        params.a.matrix.layout.layout(),
+    )?;
+    Error::check_dimension("herk", "c.rows (n)", n, params.c.rows)?;
+    Error::check_dimension("herk", "c.columns (n)", n, params.c.columns)?;
+
+    Ok((n, k))
+}
+
+pub trait RunHerk {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunHerk for Herk<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastCherk(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunHerk for Herk<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastZherk(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+    use crate::MatrixBuffer;
+
+    use super::*;
+
+    #[test]
+    fn test_complex32() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix =
+            MatrixBuffer::new_default(&pro_que, 3, 4, Complex32::new(1.0, 1.0), LayoutRowMajor);
+        let mut c_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 4, Complex32::new(0.0, 0.0), LayoutRowMajor);
+
+        let task = Herk::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .c(Hermitian::upper(&mut c_matrix))
+            .alpha(2.0)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![Complex32::new(0.0, 0.0); c_matrix.size()];
+        c_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        // (1+i) * (1-i) = 2, summed over k = 3, times alpha = 2
+        assert_eq!(out[0], Complex32::new(12.0, 0.0));
+    }
+
+    #[test]
+    fn test_conjugate_transposed() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        // stored K⨯N, so A^H is N⨯K as above
+        let a_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 3, Complex32::new(1.0, 1.0), LayoutRowMajor);
+        let mut c_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 4, Complex32::new(0.0, 0.0), LayoutRowMajor);
+
+        let task = Herk::builder()
+            .queue(&pro_que.queue())
+            .a(a_matrix.h())
+            .c(Hermitian::upper(&mut c_matrix))
+            .alpha(2.0)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![Complex32::new(0.0, 0.0); c_matrix.size()];
+        c_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        assert_eq!(out[0], Complex32::new(12.0, 0.0));
+
+        let task = Herk::builder()
+            .queue(&pro_que.queue())
+            .a(a_matrix.t())
+            .c(Hermitian::upper(&mut c_matrix))
+            .build();
+        assert!(matches!(
+            unsafe { task.run() },
+            Err(Error::UnsupportedTranspose { operand: "a", .. })
+        ));
+    }
+}
@@ -0,0 +1,391 @@
+use std::ptr;
+
+use num_complex::{Complex32, Complex64};
+use ocl::{OclPrm, Queue};
+
+use crate::{Error, KernelMode, ReprSys, VectorBuffer, VectorViewMut};
+
+use typed_builder::TypedBuilder;
+
+use clblast_sys::{CLBlastCim2col, CLBlastDim2col, CLBlastSim2col, CLBlastZim2col};
+
+/// Describes the image and kernel geometry of [`Im2col`] and [`crate::col2im::Col2im`]
+///
+/// The image is stored in NCHW order (`channels` x `height` x `width`), the column buffer holds
+/// `channels * kernel_h * kernel_w` rows of `output_height() * output_width()` values each.
+#[derive(TypedBuilder, Clone, Debug, PartialEq, Eq)]
+pub struct Im2colDescriptor {
+    /// number of channels of the image
+    pub channels: usize,
+    /// height of the image
+    pub height: usize,
+    /// width of the image
+    pub width: usize,
+
+    /// height of the kernel
+    pub kernel_h: usize,
+    /// width of the kernel
+    pub kernel_w: usize,
+
+    /// zero-padding added to the top and bottom of the image
+    #[builder(default = 0)]
+    pub pad_h: usize,
+    /// zero-padding added to the left and right of the image
+    #[builder(default = 0)]
+    pub pad_w: usize,
+
+    /// vertical distance between two kernel applications
+    #[builder(default = 1)]
+    pub stride_h: usize,
+    /// horizontal distance between two kernel applications
+    #[builder(default = 1)]
+    pub stride_w: usize,
+
+    /// vertical distance between two kernel elements
+    #[builder(default = 1)]
+    pub dilation_h: usize,
+    /// horizontal distance between two kernel elements
+    #[builder(default = 1)]
+    pub dilation_w: usize,
+}
+
+impl Im2colDescriptor {
+    /// number of kernel applications along the height of the image
+    pub fn output_height(&self) -> Result<usize, Error> {
+        output_size(
+            &HEIGHT,
+            self.height,
+            self.kernel_h,
+            self.pad_h,
+            self.stride_h,
+            self.dilation_h,
+        )
+    }
+
+    /// number of kernel applications along the width of the image
+    pub fn output_width(&self) -> Result<usize, Error> {
+        output_size(
+            &WIDTH,
+            self.width,
+            self.kernel_w,
+            self.pad_w,
+            self.stride_w,
+            self.dilation_w,
+        )
+    }
+
+    /// number of values in the image buffer
+    pub fn image_size(&self) -> Result<usize, Error> {
+        self.channels
+            .checked_mul(self.height)
+            .and_then(|size| size.checked_mul(self.width))
+            .ok_or(Error::Overflow {
+                routine: DESCRIPTOR,
+                operand: "image size",
+            })
+    }
+
+    /// number of values in the column buffer
+    pub fn col_size(&self) -> Result<usize, Error> {
+        let (output_height, output_width) = (self.output_height()?, self.output_width()?);
+        self.channels
+            .checked_mul(self.kernel_h)
+            .and_then(|size| size.checked_mul(self.kernel_w))
+            .and_then(|size| size.checked_mul(output_height))
+            .and_then(|size| size.checked_mul(output_width))
+            .ok_or(Error::Overflow {
+                routine: DESCRIPTOR,
+                operand: "col size",
+            })
+    }
+}
+
+const DESCRIPTOR: &str = "Im2colDescriptor";
+
+/// Names of the fields of one axis of [`Im2colDescriptor`], for the errors of [`output_size`]
+struct Axis {
+    kernel: &'static str,
+    stride: &'static str,
+    dilation: &'static str,
+    dilated_kernel: &'static str,
+    padded_size: &'static str,
+}
+
+const HEIGHT: Axis = Axis {
+    kernel: "kernel_h",
+    stride: "stride_h",
+    dilation: "dilation_h",
+    dilated_kernel: "dilation_h * (kernel_h - 1) + 1",
+    padded_size: "height + 2 * pad_h",
+};
+
+const WIDTH: Axis = Axis {
+    kernel: "kernel_w",
+    stride: "stride_w",
+    dilation: "dilation_w",
+    dilated_kernel: "dilation_w * (kernel_w - 1) + 1",
+    padded_size: "width + 2 * pad_w",
+};
+
+fn output_size(
+    axis: &Axis,
+    size: usize,
+    kernel: usize,
+    pad: usize,
+    stride: usize,
+    dilation: usize,
+) -> Result<usize, Error> {
+    Error::check_min_dimension(DESCRIPTOR, axis.kernel, 1, kernel)?;
+    Error::check_min_dimension(DESCRIPTOR, axis.stride, 1, stride)?;
+    Error::check_min_dimension(DESCRIPTOR, axis.dilation, 1, dilation)?;
+    let dilated_kernel = dilation
+        .checked_mul(kernel - 1)
+        .and_then(|extent| extent.checked_add(1))
+        .ok_or(Error::Overflow {
+            routine: DESCRIPTOR,
+            operand: axis.dilated_kernel,
+        })?;
+    let padded_size = pad
+        .checked_mul(2)
+        .and_then(|padding| padding.checked_add(size))
+        .ok_or(Error::Overflow {
+            routine: DESCRIPTOR,
+            operand: axis.padded_size,
+        })?;
+    // the kernel has to fit into the padded image at least once
+    Error::check_min_dimension(DESCRIPTOR, axis.padded_size, dilated_kernel, padded_size)?;
+    Ok((padded_size - dilated_kernel) / stride + 1)
+}
+
+/// Image to column transform: Rearranges the patches of the image the kernel is applied to into columns,
+/// so the convolution can be computed with a [`crate::gemm::Gemm`].
+#[derive(TypedBuilder)]
+pub struct Im2col<'a, T: OclPrm> {
+    /// OpenCL command queue associated with a context and device to execute the routine on.
+    queue: &'a Queue,
+
+    /// image and kernel geometry
+    descriptor: &'a Im2colDescriptor,
+
+    #[builder(default = KernelMode::CrossCorrelation)]
+    kernel_mode: KernelMode,
+
+    // OpenCl buffer containing the input image
+    im_buffer: &'a VectorBuffer<T>,
+    // OpenCl buffer to store the output columns
+    #[builder(setter(into))]
+    col_buffer: VectorViewMut<'a, T>,
+}
+
+pub trait RunIm2col {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+fn check_dimensions<'a, T: OclPrm>(params: &Im2col<'a, T>) -> Result<(), Error> {
+    // CLBlast reads and writes both buffers contiguously, starting at their offsets
+    let image_size = params.descriptor.image_size()?;
+    Error::check_dimension("im2col", "im_buffer.inc", 1, params.im_buffer.inc)?;
+    Error::check_min_dimension("im2col", "im_buffer.len", image_size, params.im_buffer.len)?;
+    params.im_buffer.check_extent("im2col", "im buffer")?;
+
+    let col_size = params.descriptor.col_size()?;
+    Error::check_dimension("im2col", "col_buffer.inc", 1, params.col_buffer.inc)?;
+    Error::check_min_dimension("im2col", "col_buffer.len", col_size, params.col_buffer.len)?;
+    params.col_buffer.check_extent("im2col", "col buffer")
+}
+
+impl<'a> RunIm2col for Im2col<'a, f32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastSim2col(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunIm2col for Im2col<'a, f64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastDim2col(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunIm2col for Im2col<'a, Complex32> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastCim2col(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a> RunIm2col for Im2col<'a, Complex64> {
+    unsafe fn run(self) -> Result<(), Error> {
+        check_dimensions(&self)?;
+        let d = self.descriptor;
+
+        let res = CLBlastZim2col(
+            self.kernel_mode.to_c(),
+            d.channels as u64,
+            d.height as u64,
+            d.width as u64,
+            d.kernel_h as u64,
+            d.kernel_w as u64,
+            d.pad_h as u64,
+            d.pad_w as u64,
+            d.stride_h as u64,
+            d.stride_w as u64,
+            d.dilation_h as u64,
+            d.dilation_w as u64,
+            self.im_buffer.buffer.as_ptr(),
+            self.im_buffer.offset as u64,
+            self.col_buffer.buffer.as_ptr(),
+            self.col_buffer.offset as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::VectorBuffer;
+
+    #[test]
+    fn test_output_size() {
+        let descriptor = Im2colDescriptor::builder()
+            .channels(3)
+            .height(5)
+            .width(7)
+            .kernel_h(3)
+            .kernel_w(3)
+            .pad_w(1)
+            .stride_h(2)
+            .build();
+        assert_eq!(descriptor.output_height().unwrap(), 2);
+        assert_eq!(descriptor.output_width().unwrap(), 7);
+        assert_eq!(descriptor.image_size().unwrap(), 3 * 5 * 7);
+        assert_eq!(descriptor.col_size().unwrap(), 3 * 3 * 3 * 2 * 7);
+    }
+
+    #[test]
+    fn test_invalid_geometry() {
+        let too_large = Im2colDescriptor::builder()
+            .channels(1)
+            .height(2)
+            .width(2)
+            .kernel_h(3)
+            .kernel_w(1)
+            .build();
+        assert!(matches!(
+            too_large.output_height(),
+            Err(Error::Dimension { .. })
+        ));
+
+        let overflowing = Im2colDescriptor::builder()
+            .channels(usize::MAX)
+            .height(2)
+            .width(1)
+            .kernel_h(1)
+            .kernel_w(1)
+            .dilation_w(usize::MAX)
+            .build();
+        assert!(matches!(
+            overflowing.image_size(),
+            Err(Error::Overflow { .. })
+        ));
+        assert!(overflowing.output_width().is_ok());
+    }
+
+    #[test]
+    fn test_float() {
+        use ocl::ProQue;
+        let descriptor = Im2colDescriptor::builder()
+            .channels(1)
+            .height(4)
+            .width(4)
+            .kernel_h(3)
+            .kernel_w(3)
+            .build();
+        let pro_que = ProQue::builder().src("").dims(36).build().unwrap();
+        let im_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let col_buffer = pro_que.create_buffer::<f32>().unwrap();
+        let im_vector = VectorBuffer::builder().buffer(im_buffer).build();
+        let mut col_vector = VectorBuffer::builder().buffer(col_buffer).build();
+        let task = Im2col::builder()
+            .queue(&pro_que.queue())
+            .descriptor(&descriptor)
+            .im_buffer(&im_vector)
+            .col_buffer(&mut col_vector)
+            .build();
+        unsafe { task.run().unwrap() }
+    }
+}
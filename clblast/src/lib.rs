@@ -1,4 +1,4 @@
-use std::alloc::Layout;
+use std::convert::TryFrom;
 
 use num_complex::Complex32;
 use num_complex::Complex64;
@@ -8,9 +8,21 @@ use typed_builder::TypedBuilder;
 
 use clblast_sys::cl_double2;
 use clblast_sys::cl_float2;
+use clblast_sys::CLBlastDiagonal;
+use clblast_sys::CLBlastDiagonal__CLBlastDiagonalNonUnit;
+use clblast_sys::CLBlastDiagonal__CLBlastDiagonalUnit;
+use clblast_sys::CLBlastKernelMode;
+use clblast_sys::CLBlastKernelMode__CLBlastKernelModeConvolution;
+use clblast_sys::CLBlastKernelMode__CLBlastKernelModeCrossCorrelation;
 use clblast_sys::CLBlastLayout;
 use clblast_sys::CLBlastLayout__CLBlastLayoutColMajor;
 use clblast_sys::CLBlastLayout__CLBlastLayoutRowMajor;
+use clblast_sys::CLBlastPrecision;
+use clblast_sys::CLBlastPrecision__CLBlastPrecisionComplexDouble;
+use clblast_sys::CLBlastPrecision__CLBlastPrecisionComplexSingle;
+use clblast_sys::CLBlastPrecision__CLBlastPrecisionDouble;
+use clblast_sys::CLBlastPrecision__CLBlastPrecisionHalf;
+use clblast_sys::CLBlastPrecision__CLBlastPrecisionSingle;
 use clblast_sys::CLBlastSide;
 use clblast_sys::CLBlastSide__CLBlastSideLeft;
 use clblast_sys::CLBlastSide__CLBlastSideRight;
@@ -18,25 +30,47 @@ use clblast_sys::CLBlastTranspose;
 use clblast_sys::CLBlastTranspose__CLBlastTransposeConjugate;
 use clblast_sys::CLBlastTranspose__CLBlastTransposeNo;
 use clblast_sys::CLBlastTranspose__CLBlastTransposeYes;
+use clblast_sys::CLBlastTriangle;
 use clblast_sys::CLBlastTriangle__CLBlastTriangleLower;
 use clblast_sys::CLBlastTriangle__CLBlastTriangleUpper;
-pub use result::Error;
+pub use result::{BlasError, BlastError, Error, OclError};
+pub use structured::{Hermitian, HostScalar, Symmetric, Triangular};
+pub use view::{
+    MatrixView, MatrixViewMut, TransposedBatch, TransposedView, VectorView, VectorViewMut,
+};
 
 mod amax;
 mod amin;
 mod asum;
 mod axpy;
+pub mod axpy_batched;
+pub mod cache;
+pub mod col2im;
+pub mod convgemm;
 mod copy;
 mod dot;
 mod dotc;
 pub mod gemm;
+pub mod gemm_batched;
+pub mod gemm_strided_batched;
+pub mod had;
+pub mod her2k;
+pub mod herk;
+pub mod im2col;
 mod max;
 mod min;
 mod nrm2;
+pub mod omatcopy;
+pub mod parameters;
 mod result;
 mod scal;
+mod structured;
 mod sum;
 mod swap;
+pub mod syr2k;
+pub mod trmm;
+pub mod trsm;
+mod view;
 
 pub trait ReprSys {
     type Representation;
@@ -63,14 +97,25 @@ impl ReprSys for Complex64 {
     }
 }
 
-pub trait MatrixLayout: ReprSys<Representation = CLBlastLayout> + Default {
+pub trait MatrixLayout: ReprSys<Representation = CLBlastLayout> + Clone {
     /// return the default stride (index-distance needed travel between two rows/columns of the matrix)
     /// - for [`LayoutRowMajor`] this is `columns`
     /// - for [`LayoutColMajor`] this is `rows`
-    fn default_stride(columns: usize, rows: usize) -> usize;
+    fn default_stride(&self, columns: usize, rows: usize) -> usize;
+
+    /// return the number of rows/columns the stride has to be jumped over
+    /// - for [`LayoutRowMajor`] this is `rows`
+    /// - for [`LayoutColMajor`] this is `columns`
+    fn outer_dimension(&self, columns: usize, rows: usize) -> usize;
+
+    /// return the distance of the element at (`row`, `column`) from the start of the matrix
+    fn element_offset(&self, row: usize, column: usize, stride: usize) -> usize;
+
+    /// The layout as a runtime value
+    fn layout(&self) -> Layout;
 }
 
-#[derive(Default)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct LayoutColMajor;
 impl ReprSys for LayoutColMajor {
     type Representation = CLBlastLayout;
@@ -80,12 +125,24 @@ impl ReprSys for LayoutColMajor {
     }
 }
 impl MatrixLayout for LayoutColMajor {
-    fn default_stride(columns: usize, rows: usize) -> usize {
-        rows
+    fn default_stride(&self, columns: usize, rows: usize) -> usize {
+        Layout::ColMajor.default_stride(columns, rows)
+    }
+
+    fn outer_dimension(&self, columns: usize, rows: usize) -> usize {
+        Layout::ColMajor.outer_dimension(columns, rows)
+    }
+
+    fn element_offset(&self, row: usize, column: usize, stride: usize) -> usize {
+        Layout::ColMajor.element_offset(row, column, stride)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::ColMajor
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct LayoutRowMajor;
 impl ReprSys for LayoutRowMajor {
     type Representation = CLBlastLayout;
@@ -95,17 +152,130 @@ impl ReprSys for LayoutRowMajor {
     }
 }
 impl MatrixLayout for LayoutRowMajor {
-    fn default_stride(columns: usize, rows: usize) -> usize {
-        columns
+    fn default_stride(&self, columns: usize, rows: usize) -> usize {
+        Layout::RowMajor.default_stride(columns, rows)
     }
+
+    fn outer_dimension(&self, columns: usize, rows: usize) -> usize {
+        Layout::RowMajor.outer_dimension(columns, rows)
+    }
+
+    fn element_offset(&self, row: usize, column: usize, stride: usize) -> usize {
+        Layout::RowMajor.element_offset(row, column, stride)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::RowMajor
+    }
+}
+
+/// Memory layout of a matrix as a runtime value, see [`DynLayout`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    RowMajor,
+    ColMajor,
 }
 
+impl Layout {
+    fn default_stride(self, columns: usize, rows: usize) -> usize {
+        match self {
+            Layout::RowMajor => columns,
+            Layout::ColMajor => rows,
+        }
+    }
+
+    fn outer_dimension(self, columns: usize, rows: usize) -> usize {
+        match self {
+            Layout::RowMajor => rows,
+            Layout::ColMajor => columns,
+        }
+    }
+
+    fn element_offset(self, row: usize, column: usize, stride: usize) -> usize {
+        match self {
+            Layout::RowMajor => row * stride + column,
+            Layout::ColMajor => column * stride + row,
+        }
+    }
+}
+
+impl ReprSys for Layout {
+    type Representation = CLBlastLayout;
+
+    fn to_c(&self) -> CLBlastLayout {
+        match self {
+            Layout::RowMajor => CLBlastLayout__CLBlastLayoutRowMajor,
+            Layout::ColMajor => CLBlastLayout__CLBlastLayoutColMajor,
+        }
+    }
+}
+
+/// Layout only known at runtime, e.g. read from a file header
+///
+/// Matrices of one type `MatrixBuffer<T, DynLayout>` may have different layouts,
+/// routines check that the layouts of their operands agree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynLayout(pub Layout);
+
+impl DynLayout {
+    pub fn row_major() -> Self {
+        DynLayout(Layout::RowMajor)
+    }
+
+    pub fn col_major() -> Self {
+        DynLayout(Layout::ColMajor)
+    }
+}
+
+impl From<Layout> for DynLayout {
+    fn from(layout: Layout) -> Self {
+        DynLayout(layout)
+    }
+}
+
+impl ReprSys for DynLayout {
+    type Representation = CLBlastLayout;
+
+    fn to_c(&self) -> CLBlastLayout {
+        self.0.to_c()
+    }
+}
+
+impl MatrixLayout for DynLayout {
+    fn default_stride(&self, columns: usize, rows: usize) -> usize {
+        self.0.default_stride(columns, rows)
+    }
+
+    fn outer_dimension(&self, columns: usize, rows: usize) -> usize {
+        self.0.outer_dimension(columns, rows)
+    }
+
+    fn element_offset(&self, row: usize, column: usize, stride: usize) -> usize {
+        self.0.element_offset(row, column, stride)
+    }
+
+    fn layout(&self) -> Layout {
+        self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MatrixTranspose {
     Yes,
     No,
     Conjugate,
 }
 
+impl MatrixTranspose {
+    /// Shape `(rows, columns)` of `op(M)`, for a matrix `M` with `rows` rows and `columns` columns
+    pub fn apply(&self, rows: usize, columns: usize) -> (usize, usize) {
+        match self {
+            MatrixTranspose::No => (rows, columns),
+            MatrixTranspose::Yes | MatrixTranspose::Conjugate => (columns, rows),
+        }
+    }
+}
+
 impl ReprSys for MatrixTranspose {
     type Representation = CLBlastTranspose;
     fn to_c(&self) -> CLBlastTranspose {
@@ -132,15 +302,27 @@ impl ReprSys for MultiplicationSide {
     }
 }
 
+/// Triangle of a matrix which holds the data, called `uplo` in BLAS
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TriangleLayout {
     Upper,
     Lower,
 }
 
+impl TriangleLayout {
+    /// The triangle the data ends up in when the matrix is transposed
+    pub fn flip(&self) -> TriangleLayout {
+        match self {
+            TriangleLayout::Upper => TriangleLayout::Lower,
+            TriangleLayout::Lower => TriangleLayout::Upper,
+        }
+    }
+}
+
 impl ReprSys for TriangleLayout {
-    type Representation = CLBlastLayout;
+    type Representation = CLBlastTriangle;
 
-    fn to_c(self: &Self) -> CLBlastLayout {
+    fn to_c(self: &Self) -> CLBlastTriangle {
         match self {
             TriangleLayout::Upper => CLBlastTriangle__CLBlastTriangleUpper,
             TriangleLayout::Lower => CLBlastTriangle__CLBlastTriangleLower,
@@ -148,6 +330,69 @@ impl ReprSys for TriangleLayout {
     }
 }
 
+/// Whether the diagonal of a triangular matrix is assumed to be all ones, called `diag` in BLAS
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixDiagonal {
+    Unit,
+    NonUnit,
+}
+
+impl ReprSys for MatrixDiagonal {
+    type Representation = CLBlastDiagonal;
+
+    fn to_c(self: &Self) -> CLBlastDiagonal {
+        match self {
+            MatrixDiagonal::Unit => CLBlastDiagonal__CLBlastDiagonalUnit,
+            MatrixDiagonal::NonUnit => CLBlastDiagonal__CLBlastDiagonalNonUnit,
+        }
+    }
+}
+
+/// Whether the kernel of a convolution routine is flipped (convolution) or not (cross-correlation)
+pub enum KernelMode {
+    CrossCorrelation,
+    Convolution,
+}
+
+impl ReprSys for KernelMode {
+    type Representation = CLBlastKernelMode;
+
+    fn to_c(self: &Self) -> CLBlastKernelMode {
+        match self {
+            KernelMode::CrossCorrelation => CLBlastKernelMode__CLBlastKernelModeCrossCorrelation,
+            KernelMode::Convolution => CLBlastKernelMode__CLBlastKernelModeConvolution,
+        }
+    }
+}
+
+/// Floating point precision of a routine
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Precision {
+    Half,
+    Single,
+    Double,
+    ComplexSingle,
+    ComplexDouble,
+}
+
+impl ReprSys for Precision {
+    type Representation = CLBlastPrecision;
+
+    fn to_c(self: &Self) -> CLBlastPrecision {
+        match self {
+            Precision::Half => CLBlastPrecision__CLBlastPrecisionHalf,
+            Precision::Single => CLBlastPrecision__CLBlastPrecisionSingle,
+            Precision::Double => CLBlastPrecision__CLBlastPrecisionDouble,
+            Precision::ComplexSingle => CLBlastPrecision__CLBlastPrecisionComplexSingle,
+            Precision::ComplexDouble => CLBlastPrecision__CLBlastPrecisionComplexDouble,
+        }
+    }
+}
+
+/// A matrix stored in an OpenCL buffer
+///
+/// The builder does not validate its arguments, use [`MatrixBuffer::try_build`] or
+/// [`MatrixBuffer::validate`] when the shape comes from untrusted input.
 #[derive(TypedBuilder)]
 pub struct MatrixBuffer<T: OclPrm, L: MatrixLayout> {
     buffer: Buffer<T>,
@@ -160,22 +405,68 @@ pub struct MatrixBuffer<T: OclPrm, L: MatrixLayout> {
     /// I.e. where to start
     offset: usize,
 
-    #[builder(default = L::default_stride(columns, rows))]
+    layout: L,
+
+    #[builder(default = layout.default_stride(columns, rows))]
     /// Stride: How far to jump through the outer matrix to get to next column/row
     /// In the blas nomenclature this is often called *leading dimension* or `ld`
     stride: usize,
-    layout: L,
 }
 
 impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
+    /// Panics if the buffer is too short, see [`MatrixBuffer::try_new`]
     pub fn new(columns: usize, rows: usize, buffer: Buffer<T>, layout: L) -> Self {
-        assert!(rows * columns <= buffer.len());
-        MatrixBuffer::builder()
+        Self::try_new(columns, rows, buffer, layout).unwrap()
+    }
+
+    /// Contiguous matrix starting at the beginning of the buffer
+    pub fn try_new(
+        columns: usize,
+        rows: usize,
+        buffer: Buffer<T>,
+        layout: L,
+    ) -> Result<Self, Error> {
+        let stride = layout.default_stride(columns, rows);
+        Self::try_build(columns, rows, 0, stride, buffer, layout)
+    }
+
+    /// Matrix starting at `offset`, with rows/columns (depending on the layout) `stride` values apart
+    pub fn try_build(
+        columns: usize,
+        rows: usize,
+        offset: usize,
+        stride: usize,
+        buffer: Buffer<T>,
+        layout: L,
+    ) -> Result<Self, Error> {
+        let matrix = MatrixBuffer::builder()
             .rows(rows)
             .columns(columns)
+            .offset(offset)
+            .stride(stride)
             .layout(layout)
             .buffer(buffer)
-            .build()
+            .build();
+        matrix.validate()?;
+        Ok(matrix)
+    }
+
+    /// Checks that the stride is at least the inner dimension and that the matrix fits into the buffer
+    pub fn validate(&self) -> Result<(), Error> {
+        Error::check_min_dimension(
+            "MatrixBuffer",
+            "stride",
+            self.layout.default_stride(self.columns, self.rows),
+            self.stride,
+        )?;
+        let end = self
+            .checked_extent()
+            .and_then(|extent| extent.checked_add(self.offset))
+            .ok_or(Error::Overflow {
+                routine: "MatrixBuffer",
+                operand: "offset + extent",
+            })?;
+        check_buffer_end("MatrixBuffer", end, self.buffer.len())
     }
 
     pub fn new_default(
@@ -185,9 +476,10 @@ impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
         fill_val: T,
         layout: L,
     ) -> Self {
+        let len = columns.checked_mul(rows).expect("matrix size overflows");
         let buffer = pro_que
             .buffer_builder()
-            .len(columns * rows)
+            .len(len)
             .fill_val(fill_val)
             .build()
             .unwrap();
@@ -209,6 +501,269 @@ impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
     pub fn columns(&self) -> usize {
         self.columns
     }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    /// Number of values of the buffer spanned by the matrix, counted from `offset`
+    pub fn extent(&self) -> usize {
+        self.checked_extent().expect("matrix extent overflows")
+    }
+
+    fn checked_extent(&self) -> Option<usize> {
+        if self.rows == 0 || self.columns == 0 {
+            Some(0)
+        } else {
+            (self.layout.outer_dimension(self.columns, self.rows) - 1)
+                .checked_mul(self.stride)?
+                .checked_add(self.layout.default_stride(self.columns, self.rows))
+        }
+    }
+}
+
+/// Index one past the last value of a vector of `n` elements, `stride` values apart starting at `offset`,
+/// `None` on overflow
+pub(crate) fn strided_end(offset: usize, n: usize, stride: usize) -> Option<usize> {
+    if n == 0 {
+        Some(offset)
+    } else {
+        (n - 1)
+            .checked_mul(stride)?
+            .checked_add(1)?
+            .checked_add(offset)
+    }
+}
+
+/// Number of elements of a vector, whose elements are `inc` apart in `values` values.
+/// An `inc` of 0 is treated as 1, so `validate` can report it.
+fn strided_len(values: usize, inc: usize) -> usize {
+    let inc = inc.max(1);
+    values / inc + (values % inc != 0) as usize
+}
+
+/// `end` is the index one past the last value of an operand
+fn check_buffer_end(routine: &'static str, end: usize, len: usize) -> Result<(), Error> {
+    if u64::try_from(end).is_err() {
+        return Err(Error::Overflow {
+            routine,
+            operand: "offset + extent",
+        });
+    }
+    Error::check_min_dimension(routine, "buffer", end, len)
+}
+
+/// A batch of equally shaped matrices, stored in one buffer `batch_stride` values apart
+#[derive(TypedBuilder)]
+pub struct BatchedMatrixBuffer<T: OclPrm, L: MatrixLayout> {
+    buffer: Buffer<T>,
+
+    rows: usize,
+    columns: usize,
+
+    /// Number of matrices in the batch
+    batch_count: usize,
+
+    #[builder(default = 0)]
+    /// Offset of the start of the first matrix in the buffer
+    offset: usize,
+
+    layout: L,
+
+    #[builder(default = layout.default_stride(columns, rows))]
+    /// Stride: How far to jump through the outer matrix to get to next column/row
+    /// In the blas nomenclature this is often called *leading dimension* or `ld`
+    stride: usize,
+
+    // saturates instead of wrapping, so `validate` reports the overflow
+    #[builder(default = stride.saturating_mul(layout.outer_dimension(columns, rows)))]
+    /// Batch stride: How far to jump from the start of one matrix to the start of the next one
+    batch_stride: usize,
+}
+
+impl<T: OclPrm, L: MatrixLayout> BatchedMatrixBuffer<T, L> {
+    pub fn new(
+        columns: usize,
+        rows: usize,
+        batch_count: usize,
+        buffer: Buffer<T>,
+        layout: L,
+    ) -> Self {
+        Self::try_new(columns, rows, batch_count, buffer, layout).unwrap()
+    }
+
+    /// Contiguous matrices stored directly after each other, starting at the beginning of the buffer
+    pub fn try_new(
+        columns: usize,
+        rows: usize,
+        batch_count: usize,
+        buffer: Buffer<T>,
+        layout: L,
+    ) -> Result<Self, Error> {
+        let batch = BatchedMatrixBuffer::builder()
+            .rows(rows)
+            .columns(columns)
+            .batch_count(batch_count)
+            .layout(layout)
+            .buffer(buffer)
+            .build();
+        batch.validate()?;
+        Ok(batch)
+    }
+
+    /// Batch starting at `offset`, with matrices `batch_stride` values apart,
+    /// whose rows/columns (depending on the layout) are `stride` values apart
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_build(
+        columns: usize,
+        rows: usize,
+        batch_count: usize,
+        offset: usize,
+        stride: usize,
+        batch_stride: usize,
+        buffer: Buffer<T>,
+        layout: L,
+    ) -> Result<Self, Error> {
+        let batch = BatchedMatrixBuffer::builder()
+            .rows(rows)
+            .columns(columns)
+            .batch_count(batch_count)
+            .offset(offset)
+            .stride(stride)
+            .batch_stride(batch_stride)
+            .layout(layout)
+            .buffer(buffer)
+            .build();
+        batch.validate()?;
+        Ok(batch)
+    }
+
+    /// Checks the strides, that the matrices of the batch do not overlap
+    /// and that all of them fit into the buffer
+    pub fn validate(&self) -> Result<(), Error> {
+        Error::check_min_dimension(
+            "BatchedMatrixBuffer",
+            "stride",
+            self.layout.default_stride(self.columns, self.rows),
+            self.stride,
+        )?;
+        let matrix_extent = self.matrix(0).checked_extent().ok_or(Error::Overflow {
+            routine: "BatchedMatrixBuffer",
+            operand: "matrix extent",
+        })?;
+        if self.batch_count > 1 {
+            // overlapping matrices would race, when written by a batched routine
+            Error::check_min_dimension(
+                "BatchedMatrixBuffer",
+                "batch_stride",
+                matrix_extent,
+                self.batch_stride,
+            )?;
+        }
+        let end = self
+            .checked_extent()
+            .and_then(|extent| extent.checked_add(self.offset))
+            .ok_or(Error::Overflow {
+                routine: "BatchedMatrixBuffer",
+                operand: "offset + extent",
+            })?;
+        check_buffer_end("BatchedMatrixBuffer", end, self.buffer.len())
+    }
+
+    pub fn new_default(
+        pro_que: &ocl::ProQue,
+        columns: usize,
+        rows: usize,
+        batch_count: usize,
+        fill_val: T,
+        layout: L,
+    ) -> Self {
+        let len = columns
+            .checked_mul(rows)
+            .and_then(|len| len.checked_mul(batch_count))
+            .expect("batch size overflows");
+        let buffer = pro_que
+            .buffer_builder()
+            .len(len)
+            .fill_val(fill_val)
+            .build()
+            .unwrap();
+        Self::new(columns, rows, batch_count, buffer, layout)
+    }
+
+    pub fn buffer(&self) -> &Buffer<T> {
+        &self.buffer
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn batch_count(&self) -> usize {
+        self.batch_count
+    }
+
+    /// The matrix at position `index` of the batch, panics if it is out of range
+    pub fn batch(&self, index: usize) -> MatrixBuffer<T, L> {
+        self.try_batch(index).unwrap()
+    }
+
+    /// The matrix at position `index` of the batch, sharing the buffer of the batch
+    pub fn try_batch(&self, index: usize) -> Result<MatrixBuffer<T, L>, Error> {
+        Error::check_min_dimension(
+            "batch",
+            "batch_count",
+            index.saturating_add(1),
+            self.batch_count,
+        )?;
+        let offset = index
+            .checked_mul(self.batch_stride)
+            .and_then(|offset| offset.checked_add(self.offset))
+            .ok_or(Error::Overflow {
+                routine: "batch",
+                operand: "offset",
+            })?;
+        Ok(self.matrix(offset))
+    }
+
+    /// Number of values of the buffer spanned by all matrices of the batch, counted from `offset`
+    pub fn extent(&self) -> usize {
+        self.checked_extent().expect("batch extent overflows")
+    }
+
+    fn checked_extent(&self) -> Option<usize> {
+        if self.batch_count == 0 {
+            Some(0)
+        } else {
+            (self.batch_count - 1)
+                .checked_mul(self.batch_stride)?
+                .checked_add(self.matrix(0).checked_extent()?)
+        }
+    }
+
+    /// Shape and strides of the matrices of the batch, starting at offset `offset`
+    fn matrix(&self, offset: usize) -> MatrixBuffer<T, L> {
+        MatrixBuffer::builder()
+            .buffer(self.buffer.clone())
+            .rows(self.rows)
+            .columns(self.columns)
+            .offset(offset)
+            .stride(self.stride)
+            .layout(self.layout.clone())
+            .build()
+    }
 }
 
 #[derive(TypedBuilder)]
@@ -217,6 +772,97 @@ pub struct VectorBuffer<T: OclPrm> {
 
     #[builder(default = 0)]
     offset: usize,
+
+    #[builder(default = 1)]
+    /// Increment: How far to jump through the buffer to get to the next element of the vector
+    inc: usize,
+
+    #[builder(default = strided_len(buffer.len().saturating_sub(offset), inc))]
+    /// Number of elements of the vector, starting at `offset` and `inc` values apart
+    len: usize,
+}
+
+impl<T: OclPrm> VectorBuffer<T> {
+    /// Vector of `len` consecutive values, starting at `offset` in the buffer
+    pub fn try_new(buffer: Buffer<T>, offset: usize, len: usize) -> Result<Self, Error> {
+        Self::try_strided(buffer, offset, 1, len)
+    }
+
+    /// Vector of `len` values `inc` apart, starting at `offset` in the buffer
+    pub fn try_strided(
+        buffer: Buffer<T>,
+        offset: usize,
+        inc: usize,
+        len: usize,
+    ) -> Result<Self, Error> {
+        let vector = VectorBuffer::builder()
+            .buffer(buffer)
+            .offset(offset)
+            .inc(inc)
+            .len(len)
+            .build();
+        vector.validate()?;
+        Ok(vector)
+    }
+
+    /// Checks that the increment is positive and that the vector fits into the buffer
+    pub fn validate(&self) -> Result<(), Error> {
+        Error::check_min_dimension("VectorBuffer", "inc", 1, self.inc)?;
+        let end = self
+            .checked_extent()
+            .and_then(|extent| extent.checked_add(self.offset))
+            .ok_or(Error::Overflow {
+                routine: "VectorBuffer",
+                operand: "offset + extent",
+            })?;
+        check_buffer_end("VectorBuffer", end, self.buffer.len())
+    }
+
+    pub fn buffer(&self) -> &Buffer<T> {
+        &self.buffer
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn inc(&self) -> usize {
+        self.inc
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Checks that all `len` elements of the vector lie inside its buffer
+    pub(crate) fn check_extent(
+        &self,
+        routine: &'static str,
+        operand: &'static str,
+    ) -> Result<(), Error> {
+        let end = self
+            .checked_extent()
+            .and_then(|extent| extent.checked_add(self.offset))
+            .ok_or(Error::Overflow { routine, operand })?;
+        Error::check_min_dimension(routine, operand, end, self.buffer.len())
+    }
+
+    /// Number of values of the buffer spanned by the vector, counted from `offset`
+    pub fn extent(&self) -> usize {
+        self.checked_extent().expect("vector extent overflows")
+    }
+
+    fn checked_extent(&self) -> Option<usize> {
+        if self.len == 0 {
+            Some(0)
+        } else {
+            (self.len - 1).checked_mul(self.inc)?.checked_add(1)
+        }
+    }
 }
 
 pub trait NeutralAdd {
@@ -258,3 +904,88 @@ impl NeutralMul for Complex32 {
 impl NeutralMul for Complex64 {
     const ONE: Complex64 = Complex64 { re: 1.0, im: 0.0 };
 }
+
+/// Complex element types, together with the real type of their components.
+///
+/// Used by routines like [`herk::Herk`], that take real scalars while operating on complex matrices.
+pub trait ComplexPrm: OclPrm {
+    type Real: OclPrm + NeutralAdd + NeutralMul;
+}
+
+impl ComplexPrm for Complex32 {
+    type Real = f32;
+}
+
+impl ComplexPrm for Complex64 {
+    type Real = f64;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ocl::ProQue;
+
+    #[test]
+    fn test_try_build_checks_stride_and_length() {
+        let pro_que = ProQue::builder().src("").dims(12).build().unwrap();
+        let buffer = pro_que.buffer_builder::<f32>().len(12).build().unwrap();
+
+        // 3 rows of 4 columns fit exactly
+        assert!(MatrixBuffer::try_build(4, 3, 0, 4, buffer.clone(), LayoutRowMajor).is_ok());
+        assert!(matches!(
+            MatrixBuffer::try_build(4, 3, 0, 3, buffer.clone(), LayoutRowMajor),
+            Err(Error::Dimension {
+                operand: "stride",
+                expected: 4,
+                actual: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            MatrixBuffer::try_build(4, 3, 1, 4, buffer.clone(), LayoutRowMajor),
+            Err(Error::Dimension {
+                operand: "buffer",
+                expected: 13,
+                actual: 12,
+                ..
+            })
+        ));
+        assert!(matches!(
+            MatrixBuffer::try_build(4, 3, usize::MAX, 4, buffer.clone(), LayoutRowMajor),
+            Err(Error::Overflow { .. })
+        ));
+        assert!(matches!(
+            VectorBuffer::try_new(buffer, 4, 9),
+            Err(Error::Dimension {
+                operand: "buffer",
+                expected: 13,
+                actual: 12,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_batch_try_build_rejects_overlap() {
+        let pro_que = ProQue::builder().src("").dims(12).build().unwrap();
+        let buffer = pro_que.buffer_builder::<f32>().len(12).build().unwrap();
+
+        // 3 batches of 2 x 2 matrices, 4 values apart
+        let batch =
+            BatchedMatrixBuffer::try_build(2, 2, 3, 0, 2, 4, buffer.clone(), LayoutRowMajor);
+        assert!(batch.is_ok());
+        assert!(matches!(
+            BatchedMatrixBuffer::try_build(2, 2, 3, 0, 2, 3, buffer.clone(), LayoutRowMajor),
+            Err(Error::Dimension {
+                operand: "batch_stride",
+                expected: 4,
+                actual: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            BatchedMatrixBuffer::try_build(2, 2, 3, 0, 2, usize::MAX, buffer, LayoutRowMajor),
+            Err(Error::Overflow { .. })
+        ));
+    }
+}
@@ -16,41 +16,33 @@ struct VectorMaxIndex<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to accumulate
-    n: usize,
-
     // OpenCl buffer to store the output imax vector
     imax_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output x vector
     x_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
 }
 
 trait RunVectorMaxIndex {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorMaxIndex<'a, T>) {
-    assert!(
-        params.imax_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorMaxIndex<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    params.x_vector.check_extent("max", "x buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiSmax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -61,15 +53,15 @@ impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, f32> {
 
 impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiDmax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -80,15 +72,15 @@ impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, f64> {
 
 impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiCmax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -99,15 +91,15 @@ impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, Complex32> {
 
 impl<'a> RunVectorMaxIndex for VectorMaxIndex<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastiZmax(
-            self.n as u64,
+            n as u64,
             self.imax_vector.buffer.as_ptr(),
             self.imax_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
-            self.x_stride as u64,
             self.x_vector.offset as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -133,7 +125,6 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&x_vector)
             .imax_vector(&imax_vector)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
@@ -15,41 +15,33 @@ struct VectorEuclidianNorm<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to swap
-    n: usize,
-
     // OpenCl buffer to store the output nrm vector
     nrm2_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output x vector
     x_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output y vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
 }
 
 trait RunVectorEuclidianNorm {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorEuclidianNorm<'a, T>) {
-    assert!(
-        params.x_vector.buffer.len() > params.n * params.x_stride,
-        "y buffer is too short for n and y_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorEuclidianNorm<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    params.x_vector.check_extent("nrm2", "x buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastSnrm2(
-            self.n as u64,
+            n as u64,
             self.nrm2_vector.buffer.as_ptr(),
             self.nrm2_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -60,15 +52,15 @@ impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, f32> {
 
 impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDnrm2(
-            self.n as u64,
+            n as u64,
             self.nrm2_vector.buffer.as_ptr(),
             self.nrm2_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -79,15 +71,15 @@ impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, f64> {
 
 impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastScnrm2(
-            self.n as u64,
+            n as u64,
             self.nrm2_vector.buffer.as_ptr(),
             self.nrm2_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -98,15 +90,15 @@ impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, Complex32> {
 
 impl<'a> RunVectorEuclidianNorm for VectorEuclidianNorm<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDznrm2(
-            self.n as u64,
+            n as u64,
             self.nrm2_vector.buffer.as_ptr(),
             self.nrm2_vector.offset as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -132,7 +124,6 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&x_vector)
             .nrm2_vector(&nrm2_vector)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
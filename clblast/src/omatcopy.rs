@@ -0,0 +1,245 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::Buffer;
+use ocl::OclPrm;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastComatcopy;
+use clblast_sys::CLBlastDomatcopy;
+use clblast_sys::CLBlastSomatcopy;
+use clblast_sys::CLBlastZomatcopy;
+
+use crate::Error;
+use crate::MatrixBuffer;
+use crate::MatrixLayout;
+use crate::MatrixViewMut;
+use crate::NeutralMul;
+use crate::ReprSys;
+use crate::TransposedView;
+
+/// Scaling and out-of-place transpose/copy: Computes `B := alpha * op(A)`
+///
+/// # Arguments
+/// - Matrix A: N⨯M (N Wide, M High)
+/// - Matrix B: N⨯M, or M⨯N if A is passed as `.a(a.t())` or `.a(a.h())`
+#[derive(TypedBuilder)]
+pub struct Omatcopy<'a, T, L>
+where
+    T: OclPrm + NeutralMul,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    #[builder(setter(into))]
+    a: TransposedView<'a, T, L>,
+    #[builder(setter(into))]
+    b: MatrixViewMut<'a, T, L>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+}
+
+fn check_dimensions<T: OclPrm + NeutralMul, L: MatrixLayout>(
+    params: &Omatcopy<T, L>,
+) -> Result<(usize, usize), Error> {
+    // Performs the out-of-place copy B = alpha * A, in which A (m rows by n columns) is the input matrix,
+    // B is the output matrix (m by n, or n by m if A is transposed), and alpha is a scalar value.
+
+    Error::check_layout(
+        "omatcopy",
+        "b.layout",
+        params.a.matrix.layout.layout(),
+        params.b.layout.layout(),
+    )?;
+    Error::check_dimension("omatcopy", "b.rows", params.a.rows(), params.b.rows)?;
+    Error::check_dimension(
+        "omatcopy",
+        "b.columns",
+        params.a.columns(),
+        params.b.columns,
+    )?;
+
+    Ok((params.a.matrix.rows, params.a.matrix.columns))
+}
+
+pub trait RunOmatcopy {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunOmatcopy for Omatcopy<'a, f32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastSomatcopy(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            m as u64,
+            n as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunOmatcopy for Omatcopy<'a, f64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastDomatcopy(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            m as u64,
+            n as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunOmatcopy for Omatcopy<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastComatcopy(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            m as u64,
+            n as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunOmatcopy for Omatcopy<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastZomatcopy(
+            self.a.matrix.layout.to_c(),
+            self.a.transpose.to_c(),
+            m as u64,
+            n as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+/// Allocates a new matrix of the shape of `a` on `queue` and fills it with `alpha * a`
+///
+/// Materializes a transposed view on the device: `omatcopy_new(queue, a.t(), alpha)`
+pub unsafe fn omatcopy_new<'a, T, L>(
+    queue: &Queue,
+    a: impl Into<TransposedView<'a, T, L>>,
+    alpha: T,
+) -> Result<MatrixBuffer<T, L>, Error>
+where
+    T: OclPrm + NeutralMul,
+    L: MatrixLayout,
+    for<'b> Omatcopy<'b, T, L>: RunOmatcopy,
+{
+    let a = a.into();
+    let (rows, columns) = (a.rows(), a.columns());
+    let len = rows.checked_mul(columns).ok_or(Error::Overflow {
+        routine: "omatcopy",
+        operand: "rows * columns",
+    })?;
+    let buffer = Buffer::<T>::builder()
+        .queue(queue.clone())
+        .len(len)
+        .build()?;
+    let mut b = MatrixBuffer::new(columns, rows, buffer, a.matrix.layout.clone());
+
+    Omatcopy::builder()
+        .queue(queue)
+        .a(a)
+        .b(&mut b)
+        .alpha(alpha)
+        .build()
+        .run()?;
+
+    Ok(b)
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+
+    use super::*;
+
+    #[test]
+    fn test_float_transpose() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 3, 2, 0.0f32, LayoutRowMajor);
+        a_matrix
+            .buffer()
+            .write(&vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0][..])
+            .enq()
+            .unwrap();
+
+        let b_matrix = unsafe { omatcopy_new(&pro_que.queue(), a_matrix.t(), 2.0) }.unwrap();
+        assert_eq!(b_matrix.rows(), 3);
+        assert_eq!(b_matrix.columns(), 2);
+
+        let mut out = vec![0.0; b_matrix.size()];
+        b_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        assert_eq!(out, vec![2.0, 8.0, 4.0, 10.0, 6.0, 12.0]);
+    }
+}
@@ -0,0 +1,198 @@
+//! Overriding clblast's tuning parameters of a kernel for a device.
+//!
+//! clblast ships a database of tuned parameters per device. If a device is missing in that database, or
+//! better values are known (e.g. from running clblast's tuners), they can be injected at runtime.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use ocl::core::ClDeviceIdPtr;
+use ocl::Device;
+
+use clblast_sys::CLBlastOverrideParameters;
+
+use crate::{BlastError, Error, Precision, ReprSys};
+
+/// Kernels, whose parameters can be overridden
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KernelName {
+    Xaxpy,
+    Xdot,
+    Xgemv,
+    XgemvFast,
+    XgemvFastRot,
+    Xger,
+    Xgemm,
+    XgemmDirect,
+    Xconvgemm,
+    Copy,
+    Pad,
+    Transpose,
+    Padtranspose,
+    Invert,
+    GemmRoutine,
+    TrsvRoutine,
+}
+
+impl KernelName {
+    /// name of the kernel as used by clblast
+    pub fn name(&self) -> &'static str {
+        match self {
+            KernelName::Xaxpy => "Xaxpy",
+            KernelName::Xdot => "Xdot",
+            KernelName::Xgemv => "Xgemv",
+            KernelName::XgemvFast => "XgemvFast",
+            KernelName::XgemvFastRot => "XgemvFastRot",
+            KernelName::Xger => "Xger",
+            KernelName::Xgemm => "Xgemm",
+            KernelName::XgemmDirect => "XgemmDirect",
+            KernelName::Xconvgemm => "Xconvgemm",
+            KernelName::Copy => "Copy",
+            KernelName::Pad => "Pad",
+            KernelName::Transpose => "Transpose",
+            KernelName::Padtranspose => "Padtranspose",
+            KernelName::Invert => "Invert",
+            KernelName::GemmRoutine => "GemmRoutine",
+            KernelName::TrsvRoutine => "TrsvRoutine",
+        }
+    }
+
+    /// names of all parameters of the kernel. clblast requires all of them to be overridden at once.
+    pub fn parameter_names(&self) -> &'static [&'static str] {
+        match self {
+            KernelName::Xaxpy => &["VW", "WGS", "WPT"],
+            KernelName::Xdot => &["WGS1", "WGS2"],
+            KernelName::Xgemv => &["WGS1", "WPT1", "UNROLL1"],
+            KernelName::XgemvFast => &["VW2", "WGS2", "WPT2"],
+            KernelName::XgemvFastRot => &["VW3", "WGS3", "WPT3"],
+            KernelName::Xger => &["WGS1", "WGS2", "WPT"],
+            KernelName::Xgemm => &[
+                "GEMMK", "KREG", "KWG", "KWI", "MDIMA", "MDIMC", "MWG", "NDIMB", "NDIMC", "NWG",
+                "SA", "SB", "STRM", "STRN", "VWM", "VWN",
+            ],
+            KernelName::XgemmDirect | KernelName::Xconvgemm => &[
+                "KWID", "MDIMAD", "MDIMCD", "NDIMBD", "NDIMCD", "PADA", "PADB", "VWMD", "VWND",
+                "WGD",
+            ],
+            KernelName::Copy => &["COPY_DIMX", "COPY_DIMY", "COPY_VW", "COPY_WPT"],
+            KernelName::Pad => &["PAD_DIMX", "PAD_DIMY", "PAD_WPTX", "PAD_WPTY"],
+            KernelName::Transpose => &["TRA_DIM", "TRA_PAD", "TRA_SHUFFLE", "TRA_WPT"],
+            KernelName::Padtranspose => &["PADTRA_PAD", "PADTRA_TILE", "PADTRA_WPT"],
+            KernelName::Invert => &["INTERNAL_BLOCK_SIZE"],
+            KernelName::GemmRoutine => &["XGEMM_MIN_INDIRECT_SIZE"],
+            KernelName::TrsvRoutine => &["TRSV_BLOCK_SIZE"],
+        }
+    }
+}
+
+/// Checks that `parameters` contains exactly the parameters of `kernel`
+fn validate_parameters(
+    kernel: KernelName,
+    parameters: &HashMap<String, usize>,
+) -> Result<(), Error> {
+    let known = kernel.parameter_names();
+    if let Some(unknown) = parameters
+        .keys()
+        .find(|name| !known.contains(&name.as_str()))
+    {
+        return Err(Error::UnknownParameter {
+            kernel: kernel.name(),
+            parameter: unknown.clone(),
+        });
+    }
+    if known.iter().any(|name| !parameters.contains_key(*name)) {
+        return Err(Error::Blast {
+            source: BlastError::MissingOverrideParameter,
+        });
+    }
+    Ok(())
+}
+
+/// Overrides the tuning parameters of `kernel` for `device` and `precision`.
+///
+/// `parameters` maps the parameter names (e.g. `MWG`, `NWG` and `KWG` for [`KernelName::Xgemm`]) to their values.
+/// All parameters listed in [`KernelName::parameter_names`] have to be given.
+pub fn override_parameters(
+    device: &Device,
+    kernel: KernelName,
+    precision: Precision,
+    parameters: &HashMap<String, usize>,
+) -> Result<(), Error> {
+    validate_parameters(kernel, parameters)?;
+
+    let kernel_name = CString::new(kernel.name()).unwrap();
+    let names = parameters
+        .keys()
+        .map(|name| CString::new(name.as_str()).unwrap())
+        .collect::<Vec<_>>();
+    let mut name_ptrs = names
+        .iter()
+        .map(|name| name.as_ptr())
+        .collect::<Vec<*const c_char>>();
+    let values = parameters
+        .values()
+        .map(|value| *value as u64)
+        .collect::<Vec<_>>();
+
+    let res = unsafe {
+        CLBlastOverrideParameters(
+            device.as_ptr(),
+            kernel_name.as_ptr(),
+            precision.to_c(),
+            parameters.len() as u64,
+            name_ptrs.as_mut_ptr(),
+            values.as_ptr(),
+        )
+    };
+
+    Error::from_c_either(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn xaxpy_parameters() -> HashMap<String, usize> {
+        vec![("VW", 1), ("WGS", 64), ("WPT", 1)]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_parameters() {
+        let mut parameters = xaxpy_parameters();
+        validate_parameters(KernelName::Xaxpy, &parameters).unwrap();
+
+        parameters.remove("WPT");
+        assert!(matches!(
+            validate_parameters(KernelName::Xaxpy, &parameters),
+            Err(Error::Blast {
+                source: BlastError::MissingOverrideParameter
+            })
+        ));
+
+        parameters.insert("MWG".to_string(), 64);
+        assert!(matches!(
+            validate_parameters(KernelName::Xaxpy, &parameters),
+            Err(Error::UnknownParameter {
+                kernel: "Xaxpy",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_override_xaxpy() {
+        use ocl::ProQue;
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        override_parameters(
+            &pro_que.device(),
+            KernelName::Xaxpy,
+            Precision::Single,
+            &xaxpy_parameters(),
+        )
+        .unwrap();
+    }
+}
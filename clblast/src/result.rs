@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 use clblast_sys::*;
 use ocl::ffi::c_int;
+use crate::{Layout, MatrixTranspose};
 use snafu::{Snafu};
 
 
@@ -197,6 +198,33 @@ pub enum Error {
   Blas { source: BlasError },
   Blast { source: BlastError },
   Unknown { status_code: i32 },
+  /// A parameter passed to [`crate::parameters::override_parameters`] is not known for the kernel
+  UnknownParameter { kernel: &'static str, parameter: String },
+  /// An operand does not have the dimension required by the routine.
+  /// For buffer lengths and other lower bounds `expected` is the minimum.
+  #[snafu(display("{}: {} is {}, expected {}", routine, operand, actual, expected))]
+  Dimension { routine: &'static str, operand: &'static str, expected: usize, actual: usize },
+  /// Computing the size of an operand overflows `usize` or the `u64` passed to CLBlast
+  #[snafu(display("{}: {} overflows", routine, operand))]
+  Overflow { routine: &'static str, operand: &'static str },
+  /// A matrix passed to e.g. [`crate::Symmetric::checked`] does not have the claimed structure
+  #[snafu(display("matrix is not {}: ({}, {}) does not match ({}, {})", property, row, column, column, row))]
+  Structure { property: &'static str, row: usize, column: usize },
+  /// Operands of a routine are stored in different layouts, CLBlast takes only one layout per call
+  #[snafu(display("{}: {} is {:?}, expected {:?}", routine, operand, actual, expected))]
+  LayoutMismatch { routine: &'static str, operand: &'static str, expected: Layout, actual: Layout },
+  /// The routine does not support the transpose flag, e.g. herk only takes `A` or `A^H`
+  #[snafu(display("{}: {} may not be {:?}", routine, operand, transpose))]
+  UnsupportedTranspose { routine: &'static str, operand: &'static str, transpose: MatrixTranspose },
+  /// A call into the OpenCL runtime, e.g. to allocate a buffer or read it back, failed
+  #[snafu(display("OpenCL: {}", source))]
+  Runtime { source: ocl::Error },
+}
+
+impl From<ocl::Error> for Error {
+  fn from(source: ocl::Error) -> Self {
+      Error::Runtime { source }
+  }
 }
 
 impl Error {
@@ -206,6 +234,45 @@ impl Error {
           None => Ok(()),
       }
   }
+  /// `Ok` if `actual == expected`, [`Error::Dimension`] otherwise
+  pub(crate) fn check_dimension(
+      routine: &'static str,
+      operand: &'static str,
+      expected: usize,
+      actual: usize,
+  ) -> Result<(), Error> {
+      if actual == expected {
+          Ok(())
+      } else {
+          Err(Error::Dimension { routine, operand, expected, actual })
+      }
+  }
+  /// `Ok` if `actual >= expected`, [`Error::Dimension`] otherwise
+  pub(crate) fn check_min_dimension(
+      routine: &'static str,
+      operand: &'static str,
+      expected: usize,
+      actual: usize,
+  ) -> Result<(), Error> {
+      if actual >= expected {
+          Ok(())
+      } else {
+          Err(Error::Dimension { routine, operand, expected, actual })
+      }
+  }
+  /// `Ok` if `actual == expected`, [`Error::LayoutMismatch`] otherwise
+  pub(crate) fn check_layout(
+      routine: &'static str,
+      operand: &'static str,
+      expected: Layout,
+      actual: Layout,
+  ) -> Result<(), Error> {
+      if actual == expected {
+          Ok(())
+      } else {
+          Err(Error::LayoutMismatch { routine, operand, expected, actual })
+      }
+  }
   fn from_c(status_code: c_int) -> Option<Error> {
       if status_code == CLBlastStatusCode__CLBlastSuccess {
           None
@@ -0,0 +1,573 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use num_complex::{Complex32, Complex64};
+use ocl::core::ClDeviceIdPtr;
+use ocl::{Buffer, Kernel, OclPrm, Program, Queue};
+
+use crate::{
+    Error, MatrixBuffer, MatrixDiagonal, MatrixLayout, MatrixTranspose, MatrixView, MatrixViewMut,
+    TransposedView, TriangleLayout,
+};
+
+/// A triangular matrix: only the `uplo` triangle of the wrapped matrix is read
+///
+/// `uplo` refers to the matrix as it is passed, so `Triangular::upper(a.t())` is the upper triangular `A^T`,
+/// which routines read from the lower triangle of `A`.
+///
+/// Dereferences to the wrapped matrix.
+#[derive(Clone, Copy, Debug)]
+pub struct Triangular<M> {
+    inner: M,
+    uplo: TriangleLayout,
+    diag: MatrixDiagonal,
+}
+
+impl<M> Triangular<M> {
+    pub fn new(matrix: M, uplo: TriangleLayout, diag: MatrixDiagonal) -> Self {
+        Triangular {
+            inner: matrix,
+            uplo,
+            diag,
+        }
+    }
+
+    /// Upper triangular matrix with a non-unit diagonal
+    pub fn upper(matrix: M) -> Self {
+        Self::new(matrix, TriangleLayout::Upper, MatrixDiagonal::NonUnit)
+    }
+
+    /// Lower triangular matrix with a non-unit diagonal
+    pub fn lower(matrix: M) -> Self {
+        Self::new(matrix, TriangleLayout::Lower, MatrixDiagonal::NonUnit)
+    }
+
+    /// Assume the diagonal to be all ones, without reading it
+    pub fn unit_diagonal(self) -> Self {
+        Triangular {
+            diag: MatrixDiagonal::Unit,
+            ..self
+        }
+    }
+
+    pub fn uplo(&self) -> TriangleLayout {
+        self.uplo
+    }
+
+    pub fn diag(&self) -> MatrixDiagonal {
+        self.diag
+    }
+
+    pub fn into_inner(self) -> M {
+        self.inner
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> Triangular<TransposedView<'a, T, L>> {
+    /// Triangle of the stored matrix, which is the other one if the view is transposed
+    pub(crate) fn stored_uplo(&self) -> TriangleLayout {
+        match self.inner.transpose {
+            MatrixTranspose::No => self.uplo,
+            MatrixTranspose::Yes | MatrixTranspose::Conjugate => self.uplo.flip(),
+        }
+    }
+}
+
+impl<M> Deref for Triangular<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.inner
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> From<Triangular<&'a MatrixBuffer<T, L>>>
+    for Triangular<TransposedView<'a, T, L>>
+{
+    fn from(triangular: Triangular<&'a MatrixBuffer<T, L>>) -> Self {
+        Triangular::new(triangular.inner.into(), triangular.uplo, triangular.diag)
+    }
+}
+
+impl<'a, 'b, T: OclPrm, L: MatrixLayout> From<Triangular<&'a MatrixView<'b, T, L>>>
+    for Triangular<TransposedView<'a, T, L>>
+{
+    fn from(triangular: Triangular<&'a MatrixView<'b, T, L>>) -> Self {
+        Triangular::new(triangular.inner.into(), triangular.uplo, triangular.diag)
+    }
+}
+
+/// A symmetric matrix `A = A^T`: only the `uplo` triangle of the wrapped matrix holds valid data
+///
+/// Taken as `C` by [`crate::syr2k::Syr2k`]. The other symmetric routines of CLBlast
+/// (symm, syrk, symv, ...) are not wrapped by this crate yet.
+///
+/// Dereferences to the wrapped matrix.
+#[derive(Clone, Copy, Debug)]
+pub struct Symmetric<M> {
+    inner: M,
+    uplo: TriangleLayout,
+}
+
+/// A Hermitian matrix `A = A^H`: only the `uplo` triangle of the wrapped matrix holds valid data
+///
+/// Taken as `C` by [`crate::herk::Herk`] and [`crate::her2k::Her2k`]. The other Hermitian routines of CLBlast
+/// (hemm, hemv, ...) are not wrapped by this crate yet.
+///
+/// Dereferences to the wrapped matrix.
+#[derive(Clone, Copy, Debug)]
+pub struct Hermitian<M> {
+    inner: M,
+    uplo: TriangleLayout,
+}
+
+/// Element types of [`Symmetric`] and [`Hermitian`] matrices,
+/// whose symmetry can be checked on the host and mirrored on the device
+pub trait HostScalar: OclPrm {
+    /// Name of the type in OpenCL C
+    const OPENCL_TYPE: &'static str;
+    /// Complex numbers are an OpenCL vector type of real and imaginary part
+    const COMPLEX: bool;
+
+    fn conj(self) -> Self;
+
+    /// Absolute value of the difference of two values
+    fn distance(self, other: Self) -> f64;
+}
+
+impl HostScalar for f32 {
+    const OPENCL_TYPE: &'static str = "float";
+    const COMPLEX: bool = false;
+
+    fn conj(self) -> Self {
+        self
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        (self - other).abs() as f64
+    }
+}
+
+impl HostScalar for f64 {
+    const OPENCL_TYPE: &'static str = "double";
+    const COMPLEX: bool = false;
+
+    fn conj(self) -> Self {
+        self
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        (self - other).abs()
+    }
+}
+
+impl HostScalar for Complex32 {
+    const OPENCL_TYPE: &'static str = "float2";
+    const COMPLEX: bool = true;
+
+    fn conj(self) -> Self {
+        Complex32::conj(&self)
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        (self - other).norm() as f64
+    }
+}
+
+impl HostScalar for Complex64 {
+    const OPENCL_TYPE: &'static str = "double2";
+    const COMPLEX: bool = true;
+
+    fn conj(self) -> Self {
+        Complex64::conj(&self)
+    }
+
+    fn distance(self, other: Self) -> f64 {
+        (self - other).norm()
+    }
+}
+
+impl<M> Symmetric<M> {
+    pub fn new(matrix: M, uplo: TriangleLayout) -> Self {
+        Symmetric {
+            inner: matrix,
+            uplo,
+        }
+    }
+
+    /// Symmetric matrix stored in the upper triangle
+    pub fn upper(matrix: M) -> Self {
+        Self::new(matrix, TriangleLayout::Upper)
+    }
+
+    /// Symmetric matrix stored in the lower triangle
+    pub fn lower(matrix: M) -> Self {
+        Self::new(matrix, TriangleLayout::Lower)
+    }
+
+    pub fn uplo(&self) -> TriangleLayout {
+        self.uplo
+    }
+
+    pub fn into_inner(self) -> M {
+        self.inner
+    }
+}
+
+impl<M> Hermitian<M> {
+    pub fn new(matrix: M, uplo: TriangleLayout) -> Self {
+        Hermitian {
+            inner: matrix,
+            uplo,
+        }
+    }
+
+    /// Hermitian matrix stored in the upper triangle
+    pub fn upper(matrix: M) -> Self {
+        Self::new(matrix, TriangleLayout::Upper)
+    }
+
+    /// Hermitian matrix stored in the lower triangle
+    pub fn lower(matrix: M) -> Self {
+        Self::new(matrix, TriangleLayout::Lower)
+    }
+
+    pub fn uplo(&self) -> TriangleLayout {
+        self.uplo
+    }
+
+    pub fn into_inner(self) -> M {
+        self.inner
+    }
+}
+
+impl<M, T, L> Symmetric<M>
+where
+    M: Deref<Target = MatrixBuffer<T, L>>,
+    T: HostScalar,
+    L: MatrixLayout,
+{
+    /// Like [`Symmetric::new`], but debug builds read the matrix back to the host
+    /// and check that `A[i][j]` and `A[j][i]` differ by at most `tolerance`
+    pub fn checked(matrix: M, uplo: TriangleLayout, tolerance: f64) -> Result<Self, Error> {
+        if cfg!(debug_assertions) {
+            check_mirrored(&matrix, tolerance, |value| value, "symmetric")?;
+        }
+        Ok(Self::new(matrix, uplo))
+    }
+}
+
+impl<M, T, L> Hermitian<M>
+where
+    M: Deref<Target = MatrixBuffer<T, L>>,
+    T: HostScalar,
+    L: MatrixLayout,
+{
+    /// Like [`Hermitian::new`], but debug builds read the matrix back to the host
+    /// and check that `A[i][j]` and `conj(A[j][i])` differ by at most `tolerance`
+    pub fn checked(matrix: M, uplo: TriangleLayout, tolerance: f64) -> Result<Self, Error> {
+        if cfg!(debug_assertions) {
+            check_mirrored(&matrix, tolerance, HostScalar::conj, "hermitian")?;
+        }
+        Ok(Self::new(matrix, uplo))
+    }
+}
+
+impl<M, T, L> Symmetric<M>
+where
+    M: Deref<Target = MatrixBuffer<T, L>>,
+    T: HostScalar,
+    L: MatrixLayout,
+{
+    /// Allocates a dense matrix on `queue` and fills both of its triangles with the stored one
+    pub unsafe fn to_full(&self, queue: &Queue) -> Result<MatrixBuffer<T, L>, Error> {
+        mirror_to_full(queue, &self.inner, self.uplo, false)
+    }
+}
+
+impl<M, T, L> Hermitian<M>
+where
+    M: Deref<Target = MatrixBuffer<T, L>>,
+    T: HostScalar,
+    L: MatrixLayout,
+{
+    /// Allocates a dense matrix on `queue` and fills it with the stored triangle
+    /// and the conjugate transpose of it
+    pub unsafe fn to_full(&self, queue: &Queue) -> Result<MatrixBuffer<T, L>, Error> {
+        mirror_to_full(queue, &self.inner, self.uplo, true)
+    }
+}
+
+impl<M> Deref for Symmetric<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.inner
+    }
+}
+
+impl<M> Deref for Hermitian<M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        &self.inner
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> From<Symmetric<&'a mut MatrixBuffer<T, L>>>
+    for Symmetric<MatrixViewMut<'a, T, L>>
+{
+    fn from(symmetric: Symmetric<&'a mut MatrixBuffer<T, L>>) -> Self {
+        Symmetric::new(symmetric.inner.into(), symmetric.uplo)
+    }
+}
+
+impl<'a, 'b, T: OclPrm, L: MatrixLayout> From<Symmetric<&'a mut MatrixViewMut<'b, T, L>>>
+    for Symmetric<MatrixViewMut<'a, T, L>>
+{
+    fn from(symmetric: Symmetric<&'a mut MatrixViewMut<'b, T, L>>) -> Self {
+        Symmetric::new(symmetric.inner.into(), symmetric.uplo)
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> From<Hermitian<&'a mut MatrixBuffer<T, L>>>
+    for Hermitian<MatrixViewMut<'a, T, L>>
+{
+    fn from(hermitian: Hermitian<&'a mut MatrixBuffer<T, L>>) -> Self {
+        Hermitian::new(hermitian.inner.into(), hermitian.uplo)
+    }
+}
+
+impl<'a, 'b, T: OclPrm, L: MatrixLayout> From<Hermitian<&'a mut MatrixViewMut<'b, T, L>>>
+    for Hermitian<MatrixViewMut<'a, T, L>>
+{
+    fn from(hermitian: Hermitian<&'a mut MatrixViewMut<'b, T, L>>) -> Self {
+        Hermitian::new(hermitian.inner.into(), hermitian.uplo)
+    }
+}
+
+fn check_mirrored<T: HostScalar, L: MatrixLayout>(
+    matrix: &MatrixBuffer<T, L>,
+    tolerance: f64,
+    mirror: fn(T) -> T,
+    property: &'static str,
+) -> Result<(), Error> {
+    let n = matrix.rows;
+    Error::check_dimension(property, "columns", n, matrix.columns)?;
+    if n == 0 {
+        return Ok(());
+    }
+
+    let mut values = vec![T::default(); matrix.extent()];
+    matrix
+        .buffer
+        .read(&mut values)
+        .offset(matrix.offset)
+        .enq()?;
+
+    for row in 0..n {
+        for column in row..n {
+            let value = values[matrix.layout.element_offset(row, column, matrix.stride)];
+            let mirrored = values[matrix.layout.element_offset(column, row, matrix.stride)];
+            if value.distance(mirror(mirrored)) > tolerance {
+                return Err(Error::Structure {
+                    property,
+                    row,
+                    column,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copies the stored (`upper` or lower) triangle of `a` to `b` and mirrors it into the other triangle,
+/// conjugating the mirrored values if `conjugate` is set.
+/// The unstored triangle of `a` is never read, so it may hold any garbage.
+const MIRROR_TRIANGLE_SRC: &str = r#"
+#if defined(cl_khr_fp64)
+#pragma OPENCL EXTENSION cl_khr_fp64 : enable
+#endif
+
+__kernel void mirror_triangle(
+    __global const TYPE* a, const ulong a_offset, const ulong a_row_step, const ulong a_column_step,
+    __global TYPE* b, const ulong b_row_step, const ulong b_column_step,
+    const int upper, const int conjugate)
+{
+    const ulong row = get_global_id(0);
+    const ulong column = get_global_id(1);
+    const int stored = upper ? row <= column : row >= column;
+    TYPE value = stored
+        ? a[a_offset + row * a_row_step + column * a_column_step]
+        : a[a_offset + column * a_row_step + row * a_column_step];
+#ifdef COMPLEX
+    if (conjugate && !stored) {
+        value.y = -value.y;
+    }
+#endif
+    b[row * b_row_step + column * b_column_step] = value;
+}
+"#;
+
+thread_local! {
+    /// Programs built from [`MIRROR_TRIANGLE_SRC`], by context, device and element type.
+    /// A program retains its context, so the address of a cached context is not reused.
+    static MIRROR_PROGRAMS: RefCell<HashMap<(usize, usize, &'static str), Program>> =
+        RefCell::new(HashMap::new());
+}
+
+/// [`MIRROR_TRIANGLE_SRC`] built for the device of `queue` and `T`, only compiled on first use
+fn mirror_program<T: HostScalar>(queue: &Queue) -> Result<Program, Error> {
+    let context = queue.context();
+    let device = queue.device();
+    let key = (
+        context.as_ptr() as usize,
+        device.as_ptr() as usize,
+        T::OPENCL_TYPE,
+    );
+    MIRROR_PROGRAMS.with(|programs| {
+        if let Some(program) = programs.borrow().get(&key) {
+            return Ok(program.clone());
+        }
+
+        let mut options = format!("-D TYPE={}", T::OPENCL_TYPE);
+        if T::COMPLEX {
+            options.push_str(" -D COMPLEX");
+        }
+        let program = Program::builder()
+            .devices(device)
+            .src(MIRROR_TRIANGLE_SRC)
+            .cmplr_opt(options)
+            .build(&context)?;
+        programs.borrow_mut().insert(key, program.clone());
+        Ok(program)
+    })
+}
+
+/// Runs [`MIRROR_TRIANGLE_SRC`] on `matrix`, see [`mirror_program`]
+unsafe fn mirror_to_full<T: HostScalar, L: MatrixLayout>(
+    queue: &Queue,
+    matrix: &MatrixBuffer<T, L>,
+    uplo: TriangleLayout,
+    conjugate: bool,
+) -> Result<MatrixBuffer<T, L>, Error> {
+    let n = matrix.rows;
+    Error::check_dimension("to_full", "columns", n, matrix.columns)?;
+    Error::check_min_dimension("to_full", "rows", 1, n)?;
+    let len = n.checked_mul(n).ok_or(Error::Overflow {
+        routine: "to_full",
+        operand: "rows * columns",
+    })?;
+
+    let buffer = Buffer::<T>::builder()
+        .queue(queue.clone())
+        .len(len)
+        .build()?;
+    let full = MatrixBuffer::new(n, n, buffer, matrix.layout.clone());
+
+    let program = mirror_program::<T>(queue)?;
+    let layout = &matrix.layout;
+    let mut kernel = Kernel::builder();
+    kernel
+        .program(&program)
+        .name("mirror_triangle")
+        .queue(queue.clone())
+        .global_work_size([n, n])
+        .arg(&matrix.buffer)
+        .arg(matrix.offset as u64)
+        .arg(layout.element_offset(1, 0, matrix.stride) as u64)
+        .arg(layout.element_offset(0, 1, matrix.stride) as u64)
+        .arg(&full.buffer)
+        .arg(layout.element_offset(1, 0, full.stride) as u64)
+        .arg(layout.element_offset(0, 1, full.stride) as u64)
+        .arg((uplo == TriangleLayout::Upper) as i32)
+        .arg(conjugate as i32);
+    // ocl does not know the complex types are float2 or double2
+    kernel.disable_arg_type_check();
+    kernel.build()?.enq()?;
+
+    Ok(full)
+}
+
+#[cfg(test)]
+mod test {
+    use num_complex::Complex32;
+    use ocl::ProQue;
+
+    use crate::{LayoutRowMajor, MatrixBuffer, MatrixDiagonal, TransposedView, TriangleLayout};
+
+    use super::{Hermitian, Symmetric, Triangular};
+
+    #[test]
+    fn test_transposed_triangle() {
+        let pro_que = ProQue::builder().src("").dims(9).build().unwrap();
+        let matrix = MatrixBuffer::new_default(&pro_que, 3, 3, 0.0f32, LayoutRowMajor);
+
+        let upper: Triangular<TransposedView<_, _>> = Triangular::upper(&matrix).into();
+        assert_eq!(upper.stored_uplo(), TriangleLayout::Upper);
+        assert_eq!(upper.diag(), MatrixDiagonal::NonUnit);
+
+        let upper_transposed = Triangular::upper(matrix.t()).unit_diagonal();
+        assert_eq!(upper_transposed.uplo(), TriangleLayout::Upper);
+        assert_eq!(upper_transposed.stored_uplo(), TriangleLayout::Lower);
+        assert_eq!(upper_transposed.diag(), MatrixDiagonal::Unit);
+    }
+
+    #[test]
+    fn test_symmetric_to_full() {
+        let pro_que = ProQue::builder().src("").dims(9).build().unwrap();
+        let matrix = MatrixBuffer::new_default(&pro_que, 3, 3, 0.0f32, LayoutRowMajor);
+        // only the upper triangle is valid, the lower one holds garbage
+        matrix
+            .buffer()
+            .write(&vec![1.0, 2.0, 3.0, -1.0, 4.0, 5.0, -1.0, -1.0, 6.0][..])
+            .enq()
+            .unwrap();
+
+        let symmetric = Symmetric::upper(&matrix);
+        if cfg!(debug_assertions) {
+            assert!(Symmetric::checked(&matrix, TriangleLayout::Upper, 1e-6).is_err());
+        }
+
+        let full = unsafe { symmetric.to_full(&pro_que.queue()) }.unwrap();
+        let mut out = vec![0.0; 9];
+        full.buffer().read(&mut out[..]).enq().unwrap();
+        assert_eq!(out, vec![1.0, 2.0, 3.0, 2.0, 4.0, 5.0, 3.0, 5.0, 6.0]);
+
+        assert!(Symmetric::checked(&full, TriangleLayout::Upper, 1e-6).is_ok());
+    }
+
+    #[test]
+    fn test_hermitian_to_full() {
+        let pro_que = ProQue::builder().src("").dims(4).build().unwrap();
+        let zero = Complex32::new(0.0, 0.0);
+        let matrix = MatrixBuffer::new_default(&pro_que, 2, 2, zero, LayoutRowMajor);
+        // lower triangle [[1, _], [2 + i, 3]], the upper value is garbage
+        matrix
+            .buffer()
+            .write(
+                &vec![
+                    Complex32::new(1.0, 0.0),
+                    Complex32::new(f32::NAN, f32::NAN),
+                    Complex32::new(2.0, 1.0),
+                    Complex32::new(3.0, 0.0),
+                ][..],
+            )
+            .enq()
+            .unwrap();
+
+        let full = unsafe { Hermitian::lower(&matrix).to_full(&pro_que.queue()) }.unwrap();
+        let mut out = vec![zero; 4];
+        full.buffer().read(&mut out[..]).enq().unwrap();
+        assert_eq!(
+            out,
+            vec![
+                Complex32::new(1.0, 0.0),
+                Complex32::new(2.0, -1.0),
+                Complex32::new(2.0, 1.0),
+                Complex32::new(3.0, 0.0),
+            ]
+        );
+    }
+}
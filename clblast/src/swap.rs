@@ -10,53 +10,40 @@ use typed_builder::TypedBuilder;
 use clblast_sys::{CLBlastCswap, CLBlastDswap, CLBlastSswap, CLBlastZswap};
 
 #[derive(TypedBuilder)]
-struct VectorSwap<'a, T: OclPrm> {
+pub(crate) struct VectorSwap<'a, T: OclPrm> {
     /// OpenCL command queue associated with a context and device to execute the routine on.
     queue: &'a Queue,
 
-    /// number of values to swap
-    n: usize,
-
     // OpenCl buffer to store the output x vector
     x_vector: &'a VectorBuffer<T>,
     // OpenCl buffer to store the output y vector
     y_vector: &'a VectorBuffer<T>,
-
-    /// Stride/increment of the output x vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    x_stride: usize,
-    /// Stride/increment of the output y vector. This value must be greater than 0.
-    #[builder(default = 1)]
-    y_stride: usize,
 }
 
-trait RunVectorSwap {
+pub(crate) trait RunVectorSwap {
     unsafe fn run(self) -> Result<(), Error>;
 }
 
-fn assert_dimensions<'a, T: OclPrm>(params: &VectorSwap<'a, T>) {
-    assert!(
-        params.x_vector.buffer.len() > params.n * params.x_stride,
-        "x buffer is too short for n and x_stride"
-    );
-    assert!(
-        params.y_vector.buffer.len() > params.n * params.y_stride,
-        "y buffer is too short for n and y_stride"
-    );
+fn check_dimensions<'a, T: OclPrm>(params: &VectorSwap<'a, T>) -> Result<usize, Error> {
+    let n = params.x_vector.len;
+    Error::check_dimension("swap", "y.len (n)", n, params.y_vector.len)?;
+    params.x_vector.check_extent("swap", "x buffer")?;
+    params.y_vector.check_extent("swap", "y buffer")?;
+    Ok(n)
 }
 
 impl<'a> RunVectorSwap for VectorSwap<'a, f32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastSswap(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -67,16 +54,16 @@ impl<'a> RunVectorSwap for VectorSwap<'a, f32> {
 
 impl<'a> RunVectorSwap for VectorSwap<'a, f64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastDswap(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -87,16 +74,16 @@ impl<'a> RunVectorSwap for VectorSwap<'a, f64> {
 
 impl<'a> RunVectorSwap for VectorSwap<'a, Complex32> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastCswap(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -107,16 +94,16 @@ impl<'a> RunVectorSwap for VectorSwap<'a, Complex32> {
 
 impl<'a> RunVectorSwap for VectorSwap<'a, Complex64> {
     unsafe fn run(self) -> Result<(), Error> {
-        assert_dimensions(&self);
+        let n = check_dimensions(&self)?;
 
         let res = CLBlastZswap(
-            self.n as u64,
+            n as u64,
             self.x_vector.buffer.as_ptr(),
             self.x_vector.offset as u64,
-            self.x_stride as u64,
+            self.x_vector.inc as u64,
             self.y_vector.buffer.as_ptr(),
             self.y_vector.offset as u64,
-            self.y_stride as u64,
+            self.y_vector.inc as u64,
             &mut self.queue.as_ptr(),
             &mut ptr::null_mut(),
         );
@@ -142,7 +129,6 @@ mod test {
             .queue(&pro_que.queue())
             .x_vector(&a_matrix)
             .y_vector(&b_matrix)
-            .n(10)
             .build();
         unsafe { task.run().unwrap() }
     }
@@ -0,0 +1,280 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::OclPrm;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastCsyr2k;
+use clblast_sys::CLBlastDsyr2k;
+use clblast_sys::CLBlastSsyr2k;
+use clblast_sys::CLBlastZsyr2k;
+
+use crate::Error;
+use crate::MatrixLayout;
+use crate::MatrixTranspose;
+use crate::MatrixViewMut;
+use crate::NeutralAdd;
+use crate::NeutralMul;
+use crate::ReprSys;
+use crate::Symmetric;
+use crate::TransposedView;
+
+/// Symmetric rank-2K update: Computes `C := alpha * A * B^T + alpha * B * A^T + beta * C`
+/// or `C := alpha * A^T * B + alpha * B^T * A + beta * C`
+///
+/// Only the triangle recorded in the [`Symmetric`] wrapper of `C` is read and updated.
+///
+/// # Arguments
+/// - Matrix A: K⨯N (K Wide, N High)
+/// - Matrix B: same shape as A
+/// - Matrix C: N⨯N (Symmetric)
+///
+/// A and B can be passed transposed with `.a(a.t()).b(b.t())`, the shapes above then refer to
+/// `A^T` and `B^T`. CLBlast applies one transpose flag to both, and rejects a conjugate transpose.
+#[derive(TypedBuilder)]
+pub struct Syr2k<'a, T, L>
+where
+    T: OclPrm + NeutralAdd + NeutralMul,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    #[builder(setter(into))]
+    a: TransposedView<'a, T, L>,
+    #[builder(setter(into))]
+    b: TransposedView<'a, T, L>,
+    /// e.g. `.c(Symmetric::upper(&mut c))`, only the recorded triangle is updated
+    #[builder(setter(into))]
+    c: Symmetric<MatrixViewMut<'a, T, L>>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+    #[builder(default=NeutralAdd::ZERO)]
+    beta: T,
+}
+
+fn check_dimensions<T: OclPrm + NeutralAdd + NeutralMul, L: MatrixLayout>(
+    params: &Syr2k<T, L>,
+) -> Result<(usize, usize), Error> {
+    // Performs the matrix product C = alpha * A * B^T + alpha * B * A^T + beta * C or
+    // C = alpha * A^T * B + alpha * B^T * A + beta * C, in which A and B (n rows by k columns) are two
+    // general matrices (transposed in the second case), C (n rows by n columns) is the symmetric
+    // matrix to be updated, and alpha and beta are scalar values.
+
+    if params.a.transpose == MatrixTranspose::Conjugate {
+        return Err(Error::UnsupportedTranspose {
+            routine: "syr2k",
+            operand: "a",
+            transpose: MatrixTranspose::Conjugate,
+        });
+    }
+    // CLBlast takes a single transpose flag for A and B
+    if params.b.transpose != params.a.transpose {
+        return Err(Error::UnsupportedTranspose {
+            routine: "syr2k",
+            operand: "b",
+            transpose: params.b.transpose,
+        });
+    }
+    let (n, k) = (params.a.rows(), params.a.columns());
+
+    let layout = params.c.layout.layout();
+    Error::check_layout("syr2k", "a.layout", layout, params.a.matrix.layout.layout())?;
+    Error::check_layout("syr2k", "b.layout", layout, params.b.matrix.layout.layout())?;
+    Error::check_dimension("syr2k", "b.rows", n, params.b.rows())?;
+    Error::check_dimension("syr2k", "b.columns", k, params.b.columns())?;
+    Error::check_dimension("syr2k", "c.rows (n)", n, params.c.rows)?;
+    Error::check_dimension("syr2k", "c.columns (n)", n, params.c.columns)?;
+
+    Ok((n, k))
+}
+
+pub trait RunSyr2k {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunSyr2k for Syr2k<'a, f32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastSsyr2k(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            self.b.matrix.offset as u64,
+            self.b.matrix.stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunSyr2k for Syr2k<'a, f64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastDsyr2k(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            self.b.matrix.offset as u64,
+            self.b.matrix.stride as u64,
+            self.beta,
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunSyr2k for Syr2k<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastCsyr2k(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            self.b.matrix.offset as u64,
+            self.b.matrix.stride as u64,
+            self.beta.to_c(),
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunSyr2k for Syr2k<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (n, k) = check_dimensions(&self)?;
+
+        let res = CLBlastZsyr2k(
+            self.a.matrix.layout.to_c(),
+            self.c.uplo().to_c(),
+            self.a.transpose.to_c(),
+            n as u64,
+            k as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.matrix.buffer.as_ptr(),
+            self.b.matrix.offset as u64,
+            self.b.matrix.stride as u64,
+            self.beta.to_c(),
+            self.c.buffer.as_ptr(),
+            self.c.offset as u64,
+            self.c.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+    use crate::MatrixBuffer;
+
+    use super::*;
+
+    #[test]
+    fn test_float() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 3, 4, 1.0f32, LayoutRowMajor);
+        let b_matrix = MatrixBuffer::new_default(&pro_que, 3, 4, 2.0f32, LayoutRowMajor);
+        let mut c_matrix = MatrixBuffer::new_default(&pro_que, 4, 4, 0.0f32, LayoutRowMajor);
+
+        let task = Syr2k::builder()
+            .queue(&pro_que.queue())
+            .a(&a_matrix)
+            .b(&b_matrix)
+            .c(Symmetric::upper(&mut c_matrix))
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![0.0; c_matrix.size()];
+        c_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        // 1 * 2 summed over k = 3, once for A * B^T and once for B * A^T
+        assert_eq!(out[0], 12.0);
+    }
+
+    #[test]
+    fn test_reject_conjugate() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 3, Complex32::new(1.0, 0.0), LayoutRowMajor);
+        let b_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 3, Complex32::new(0.0, 1.0), LayoutRowMajor);
+        let mut c_matrix =
+            MatrixBuffer::new_default(&pro_que, 4, 4, Complex32::new(0.0, 0.0), LayoutRowMajor);
+
+        let task = Syr2k::builder()
+            .queue(&pro_que.queue())
+            .a(a_matrix.h())
+            .b(b_matrix.h())
+            .c(Symmetric::upper(&mut c_matrix))
+            .build();
+        assert!(matches!(
+            unsafe { task.run() },
+            Err(Error::UnsupportedTranspose { operand: "a", .. })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+use std::ptr;
+
+use num_complex::Complex32;
+use num_complex::Complex64;
+use ocl::OclPrm;
+use ocl::Queue;
+use typed_builder::TypedBuilder;
+
+use clblast_sys::CLBlastCtrsm;
+use clblast_sys::CLBlastDtrsm;
+use clblast_sys::CLBlastStrsm;
+use clblast_sys::CLBlastZtrsm;
+
+use crate::Error;
+use crate::MatrixLayout;
+use crate::MatrixViewMut;
+use crate::MultiplicationSide;
+use crate::NeutralMul;
+use crate::ReprSys;
+use crate::TransposedView;
+use crate::Triangular;
+
+/// Solves a triangular system of equations: Computes `X` in `op(A) * X = alpha * B` or `X * op(A) = alpha * B`
+///
+/// `A` is triangular, `B` is overwritten with the solution `X`.
+///
+/// # Arguments
+/// - Matrix A: M⨯M if `side` is [`MultiplicationSide::Left`], N⨯N if `side` is [`MultiplicationSide::Right`]
+/// - Matrix B: N⨯M (N Wide, M High)
+#[derive(TypedBuilder)]
+pub struct Trsm<'a, T, L>
+where
+    T: OclPrm + NeutralMul,
+    L: MatrixLayout,
+{
+    //Queue
+    queue: &'a Queue,
+
+    // Matrices
+    /// Triangular matrix, e.g. `.a(Triangular::upper(&a))` or `.a(Triangular::lower(a.t()).unit_diagonal())`
+    #[builder(setter(into))]
+    a: Triangular<TransposedView<'a, T, L>>,
+    #[builder(setter(into))]
+    b: MatrixViewMut<'a, T, L>,
+
+    // factors
+    #[builder(default=NeutralMul::ONE)]
+    alpha: T,
+
+    /// Whether `A` is on the left or the right of `X`
+    #[builder(default=MultiplicationSide::Left)]
+    side: MultiplicationSide,
+}
+
+fn check_dimensions<T: OclPrm + NeutralMul, L: MatrixLayout>(
+    params: &Trsm<T, L>,
+) -> Result<(usize, usize), Error> {
+    // Solves the equation A * X = alpha * B or X * A = alpha * B for the unknown m by n matrix X,
+    // in which A is a unit or non-unit triangular matrix (m by m or n by n),
+    // B (m rows by n columns) is overwritten with the solution X, and alpha is a scalar value.
+    // The matrix A can optionally be transposed before solving the equation.
+
+    let m = params.b.rows;
+    let n = params.b.columns;
+
+    Error::check_layout(
+        "trsm",
+        "a.layout",
+        params.b.layout.layout(),
+        params.a.matrix.layout.layout(),
+    )?;
+    Error::check_dimension(
+        "trsm",
+        "a.columns",
+        params.a.matrix.rows,
+        params.a.matrix.columns,
+    )?;
+    let (side, operand) = match params.side {
+        MultiplicationSide::Left => (m, "a.rows (m)"),
+        MultiplicationSide::Right => (n, "a.rows (n)"),
+    };
+    Error::check_dimension("trsm", operand, side, params.a.matrix.rows)?;
+
+    Ok((m, n))
+}
+
+pub trait RunTrsm {
+    unsafe fn run(self) -> Result<(), Error>;
+}
+
+impl<'a, L> RunTrsm for Trsm<'a, f32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastStrsm(
+            self.a.matrix.layout.to_c(),
+            self.side.to_c(),
+            self.a.stored_uplo().to_c(),
+            self.a.transpose.to_c(),
+            self.a.diag().to_c(),
+            m as u64,
+            n as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunTrsm for Trsm<'a, f64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastDtrsm(
+            self.a.matrix.layout.to_c(),
+            self.side.to_c(),
+            self.a.stored_uplo().to_c(),
+            self.a.transpose.to_c(),
+            self.a.diag().to_c(),
+            m as u64,
+            n as u64,
+            self.alpha,
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunTrsm for Trsm<'a, Complex32, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastCtrsm(
+            self.a.matrix.layout.to_c(),
+            self.side.to_c(),
+            self.a.stored_uplo().to_c(),
+            self.a.transpose.to_c(),
+            self.a.diag().to_c(),
+            m as u64,
+            n as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+impl<'a, L> RunTrsm for Trsm<'a, Complex64, L>
+where
+    L: MatrixLayout,
+{
+    unsafe fn run(self) -> Result<(), Error> {
+        let (m, n) = check_dimensions(&self)?;
+
+        let res = CLBlastZtrsm(
+            self.a.matrix.layout.to_c(),
+            self.side.to_c(),
+            self.a.stored_uplo().to_c(),
+            self.a.transpose.to_c(),
+            self.a.diag().to_c(),
+            m as u64,
+            n as u64,
+            self.alpha.to_c(),
+            self.a.matrix.buffer.as_ptr(),
+            self.a.matrix.offset as u64,
+            self.a.matrix.stride as u64,
+            self.b.buffer.as_ptr(),
+            self.b.offset as u64,
+            self.b.stride as u64,
+            &mut self.queue.as_ptr(),
+            &mut ptr::null_mut(),
+        );
+
+        Error::from_c_either(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ocl::ProQue;
+
+    use crate::LayoutRowMajor;
+    use crate::MatrixBuffer;
+
+    use super::*;
+
+    #[test]
+    fn test_float() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let a_matrix = MatrixBuffer::new_default(&pro_que, 3, 3, 2.0f32, LayoutRowMajor);
+        let mut b_matrix = MatrixBuffer::new_default(&pro_que, 2, 3, 1.0f32, LayoutRowMajor);
+        b_matrix
+            .buffer()
+            .write(&vec![5.0, 5.0, 3.0, 3.0, 1.0, 1.0][..])
+            .enq()
+            .unwrap();
+
+        let task = Trsm::builder()
+            .queue(&pro_que.queue())
+            .a(Triangular::upper(&a_matrix).unit_diagonal())
+            .b(&mut b_matrix)
+            .build();
+        unsafe { task.run().unwrap() }
+
+        let mut out = vec![0.0; b_matrix.size()];
+        b_matrix.buffer().read(&mut out[..]).enq().unwrap();
+        // inverse of the example in `trmm`
+        assert_eq!(out, vec![1.0; 6]);
+    }
+}
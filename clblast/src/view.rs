@@ -0,0 +1,776 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, Range};
+
+use ocl::OclPrm;
+
+use crate::{
+    BatchedMatrixBuffer, Error, MatrixBuffer, MatrixLayout, MatrixTranspose, VectorBuffer,
+};
+
+/// Read-only block of a [`MatrixBuffer`], see [`MatrixBuffer::view`]
+///
+/// Dereferences to a [`MatrixBuffer`] with the `offset` of the block and the `stride` of the parent,
+/// so it can be passed to every routine accepting a `&MatrixBuffer`.
+pub struct MatrixView<'a, T: OclPrm, L: MatrixLayout> {
+    matrix: MatrixBuffer<T, L>,
+    parent: PhantomData<&'a MatrixBuffer<T, L>>,
+}
+
+/// Mutable block of a [`MatrixBuffer`], see [`MatrixBuffer::view_mut`]
+///
+/// Dereferences read-only to a [`MatrixBuffer`] with the `offset` of the block and the `stride` of the parent.
+/// Routines writing to a matrix take a `MatrixViewMut`, so `.c(&mut block)` works as well as `.c(&mut matrix)`.
+pub struct MatrixViewMut<'a, T: OclPrm, L: MatrixLayout> {
+    matrix: MatrixBuffer<T, L>,
+    parent: PhantomData<&'a mut MatrixBuffer<T, L>>,
+}
+
+impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
+    /// The block of `rows` and `columns` of the matrix, panics if they are out of range
+    pub fn view(&self, rows: Range<usize>, columns: Range<usize>) -> MatrixView<'_, T, L> {
+        self.try_view(rows, columns).unwrap()
+    }
+
+    /// The block of `rows` and `columns` of the matrix
+    pub fn try_view(
+        &self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> Result<MatrixView<'_, T, L>, Error> {
+        Ok(MatrixView {
+            matrix: self.block(rows, columns)?,
+            parent: PhantomData,
+        })
+    }
+
+    /// Mutable block of `rows` and `columns` of the matrix, panics if they are out of range
+    pub fn view_mut(
+        &mut self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> MatrixViewMut<'_, T, L> {
+        self.try_view_mut(rows, columns).unwrap()
+    }
+
+    /// Mutable block of `rows` and `columns` of the matrix
+    pub fn try_view_mut(
+        &mut self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> Result<MatrixViewMut<'_, T, L>, Error> {
+        Ok(MatrixViewMut {
+            matrix: self.block(rows, columns)?,
+            parent: PhantomData,
+        })
+    }
+
+    fn block(&self, rows: Range<usize>, columns: Range<usize>) -> Result<Self, Error> {
+        Error::check_min_dimension("view", "rows.end", rows.start, rows.end)?;
+        Error::check_min_dimension("view", "columns.end", columns.start, columns.end)?;
+        Error::check_min_dimension("view", "rows", rows.end, self.rows)?;
+        Error::check_min_dimension("view", "columns", columns.end, self.columns)?;
+
+        let offset = self
+            .offset
+            .checked_add(
+                self.layout
+                    .element_offset(rows.start, columns.start, self.stride),
+            )
+            .ok_or(Error::Overflow {
+                routine: "view",
+                operand: "offset",
+            })?;
+        Ok(MatrixBuffer::builder()
+            .buffer(self.buffer.clone())
+            .rows(rows.len())
+            .columns(columns.len())
+            .offset(offset)
+            .stride(self.stride)
+            .layout(self.layout.clone())
+            .build())
+    }
+
+    /// Splits the matrix into the mutable blocks of rows `..row` and `row..`,
+    /// panics if `row` is out of range
+    pub fn split_rows_mut(
+        &mut self,
+        row: usize,
+    ) -> (MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>) {
+        self.try_split_rows_mut(row).unwrap()
+    }
+
+    /// Splits the matrix into the mutable blocks of rows `..row` and `row..`
+    ///
+    /// The blocks do not overlap, so e.g. one can be read while the other one is written.
+    pub fn try_split_rows_mut(
+        &mut self,
+        row: usize,
+    ) -> Result<(MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>), Error> {
+        let (rows, columns) = (self.rows, self.columns);
+        self.split(0..row, 0..columns, row..rows, 0..columns)
+    }
+
+    /// Splits the matrix into the mutable blocks of columns `..column` and `column..`,
+    /// panics if `column` is out of range
+    pub fn split_columns_mut(
+        &mut self,
+        column: usize,
+    ) -> (MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>) {
+        self.try_split_columns_mut(column).unwrap()
+    }
+
+    /// Splits the matrix into the mutable blocks of columns `..column` and `column..`
+    ///
+    /// The blocks do not overlap, so e.g. one can be read while the other one is written.
+    pub fn try_split_columns_mut(
+        &mut self,
+        column: usize,
+    ) -> Result<(MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>), Error> {
+        let (rows, columns) = (self.rows, self.columns);
+        self.split(0..rows, 0..column, 0..rows, column..columns)
+    }
+
+    fn split(
+        &mut self,
+        first_rows: Range<usize>,
+        first_columns: Range<usize>,
+        second_rows: Range<usize>,
+        second_columns: Range<usize>,
+    ) -> Result<(MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>), Error> {
+        // with a shorter stride rows/columns of the matrix overlap in the buffer
+        Error::check_min_dimension(
+            "split",
+            "stride",
+            self.layout.default_stride(self.columns, self.rows),
+            self.stride,
+        )?;
+        let first = MatrixViewMut {
+            matrix: self.block(first_rows, first_columns)?,
+            parent: PhantomData,
+        };
+        let second = MatrixViewMut {
+            matrix: self.block(second_rows, second_columns)?,
+            parent: PhantomData,
+        };
+        Ok((first, second))
+    }
+}
+
+/// Mutable blocks and vectors of a mutable block, borrowing it like those of [`MatrixBuffer`] borrow the matrix
+impl<'a, T: OclPrm, L: MatrixLayout> MatrixViewMut<'a, T, L> {
+    /// See [`MatrixBuffer::view_mut`]
+    pub fn view_mut(
+        &mut self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> MatrixViewMut<'_, T, L> {
+        self.matrix.view_mut(rows, columns)
+    }
+
+    /// See [`MatrixBuffer::try_view_mut`]
+    pub fn try_view_mut(
+        &mut self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> Result<MatrixViewMut<'_, T, L>, Error> {
+        self.matrix.try_view_mut(rows, columns)
+    }
+
+    /// See [`MatrixBuffer::split_rows_mut`]
+    pub fn split_rows_mut(
+        &mut self,
+        row: usize,
+    ) -> (MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>) {
+        self.matrix.split_rows_mut(row)
+    }
+
+    /// See [`MatrixBuffer::try_split_rows_mut`]
+    pub fn try_split_rows_mut(
+        &mut self,
+        row: usize,
+    ) -> Result<(MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>), Error> {
+        self.matrix.try_split_rows_mut(row)
+    }
+
+    /// See [`MatrixBuffer::split_columns_mut`]
+    pub fn split_columns_mut(
+        &mut self,
+        column: usize,
+    ) -> (MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>) {
+        self.matrix.split_columns_mut(column)
+    }
+
+    /// See [`MatrixBuffer::try_split_columns_mut`]
+    pub fn try_split_columns_mut(
+        &mut self,
+        column: usize,
+    ) -> Result<(MatrixViewMut<'_, T, L>, MatrixViewMut<'_, T, L>), Error> {
+        self.matrix.try_split_columns_mut(column)
+    }
+
+    /// See [`MatrixBuffer::row_mut`]
+    pub fn row_mut(&mut self, row: usize) -> VectorViewMut<'_, T> {
+        self.matrix.row_mut(row)
+    }
+
+    /// See [`MatrixBuffer::try_row_mut`]
+    pub fn try_row_mut(&mut self, row: usize) -> Result<VectorViewMut<'_, T>, Error> {
+        self.matrix.try_row_mut(row)
+    }
+
+    /// See [`MatrixBuffer::column_mut`]
+    pub fn column_mut(&mut self, column: usize) -> VectorViewMut<'_, T> {
+        self.matrix.column_mut(column)
+    }
+
+    /// See [`MatrixBuffer::try_column_mut`]
+    pub fn try_column_mut(&mut self, column: usize) -> Result<VectorViewMut<'_, T>, Error> {
+        self.matrix.try_column_mut(column)
+    }
+
+    /// See [`MatrixBuffer::diagonal_mut`]
+    pub fn diagonal_mut(&mut self) -> VectorViewMut<'_, T> {
+        self.matrix.diagonal_mut()
+    }
+}
+
+/// Read-only row, column or diagonal of a [`MatrixBuffer`], see [`MatrixBuffer::row`]
+///
+/// Dereferences to a strided [`VectorBuffer`], so it can be passed to every routine accepting a `&VectorBuffer`.
+pub struct VectorView<'a, T: OclPrm> {
+    vector: VectorBuffer<T>,
+    parent: PhantomData<&'a VectorBuffer<T>>,
+}
+
+/// Mutable row, column or diagonal of a [`MatrixBuffer`], see [`MatrixBuffer::row_mut`]
+///
+/// Dereferences read-only to a strided [`VectorBuffer`]. Routines writing to a vector take a `VectorViewMut`,
+/// so `.z_vector(&mut column)` works as well as `.z_vector(&mut vector)`.
+pub struct VectorViewMut<'a, T: OclPrm> {
+    vector: VectorBuffer<T>,
+    parent: PhantomData<&'a mut VectorBuffer<T>>,
+}
+
+impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
+    /// Row `row` of the matrix, panics if it is out of range
+    pub fn row(&self, row: usize) -> VectorView<'_, T> {
+        self.try_row(row).unwrap()
+    }
+
+    /// Row `row` of the matrix
+    pub fn try_row(&self, row: usize) -> Result<VectorView<'_, T>, Error> {
+        Ok(VectorView {
+            vector: self.row_vector(row)?,
+            parent: PhantomData,
+        })
+    }
+
+    /// Column `column` of the matrix, panics if it is out of range
+    pub fn column(&self, column: usize) -> VectorView<'_, T> {
+        self.try_column(column).unwrap()
+    }
+
+    /// Column `column` of the matrix
+    pub fn try_column(&self, column: usize) -> Result<VectorView<'_, T>, Error> {
+        Ok(VectorView {
+            vector: self.column_vector(column)?,
+            parent: PhantomData,
+        })
+    }
+
+    /// Main diagonal of the matrix
+    pub fn diagonal(&self) -> VectorView<'_, T> {
+        VectorView {
+            vector: self.diagonal_vector(),
+            parent: PhantomData,
+        }
+    }
+
+    /// Mutable row `row` of the matrix, panics if it is out of range
+    pub fn row_mut(&mut self, row: usize) -> VectorViewMut<'_, T> {
+        self.try_row_mut(row).unwrap()
+    }
+
+    /// Mutable row `row` of the matrix
+    pub fn try_row_mut(&mut self, row: usize) -> Result<VectorViewMut<'_, T>, Error> {
+        Ok(VectorViewMut {
+            vector: self.row_vector(row)?,
+            parent: PhantomData,
+        })
+    }
+
+    /// Mutable column `column` of the matrix, panics if it is out of range
+    pub fn column_mut(&mut self, column: usize) -> VectorViewMut<'_, T> {
+        self.try_column_mut(column).unwrap()
+    }
+
+    /// Mutable column `column` of the matrix
+    pub fn try_column_mut(&mut self, column: usize) -> Result<VectorViewMut<'_, T>, Error> {
+        Ok(VectorViewMut {
+            vector: self.column_vector(column)?,
+            parent: PhantomData,
+        })
+    }
+
+    /// Mutable main diagonal of the matrix
+    pub fn diagonal_mut(&mut self) -> VectorViewMut<'_, T> {
+        VectorViewMut {
+            vector: self.diagonal_vector(),
+            parent: PhantomData,
+        }
+    }
+
+    fn row_vector(&self, row: usize) -> Result<VectorBuffer<T>, Error> {
+        Error::check_min_dimension("row", "rows", row.saturating_add(1), self.rows)?;
+        // neighbouring elements of a row are one column apart
+        self.strided_vector(
+            row,
+            0,
+            self.layout.element_offset(0, 1, self.stride),
+            self.columns,
+        )
+    }
+
+    fn column_vector(&self, column: usize) -> Result<VectorBuffer<T>, Error> {
+        Error::check_min_dimension("column", "columns", column.saturating_add(1), self.columns)?;
+        self.strided_vector(
+            0,
+            column,
+            self.layout.element_offset(1, 0, self.stride),
+            self.rows,
+        )
+    }
+
+    fn diagonal_vector(&self) -> VectorBuffer<T> {
+        let len = self.rows.min(self.columns);
+        // starts at the first element of the matrix, so the offset cannot overflow
+        self.strided_vector(0, 0, self.layout.element_offset(1, 1, self.stride), len)
+            .unwrap()
+    }
+
+    fn strided_vector(
+        &self,
+        row: usize,
+        column: usize,
+        inc: usize,
+        len: usize,
+    ) -> Result<VectorBuffer<T>, Error> {
+        let offset = self
+            .offset
+            .checked_add(self.layout.element_offset(row, column, self.stride))
+            .ok_or(Error::Overflow {
+                routine: "view",
+                operand: "offset",
+            })?;
+        Ok(VectorBuffer::builder()
+            .buffer(self.buffer.clone())
+            .offset(offset)
+            .inc(inc)
+            .len(len)
+            .build())
+    }
+}
+
+impl<'a, T: OclPrm> Deref for VectorView<'a, T> {
+    type Target = VectorBuffer<T>;
+
+    fn deref(&self) -> &VectorBuffer<T> {
+        &self.vector
+    }
+}
+
+impl<'a, T: OclPrm> Deref for VectorViewMut<'a, T> {
+    type Target = VectorBuffer<T>;
+
+    fn deref(&self) -> &VectorBuffer<T> {
+        &self.vector
+    }
+}
+
+impl<'a, T: OclPrm> From<&'a mut VectorBuffer<T>> for VectorViewMut<'a, T> {
+    fn from(vector: &'a mut VectorBuffer<T>) -> Self {
+        VectorViewMut {
+            vector: vector.alias(),
+            parent: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T: OclPrm> From<&'a mut VectorViewMut<'b, T>> for VectorViewMut<'a, T> {
+    fn from(view: &'a mut VectorViewMut<'b, T>) -> Self {
+        VectorViewMut {
+            vector: view.vector.alias(),
+            parent: PhantomData,
+        }
+    }
+}
+
+impl<T: OclPrm> VectorBuffer<T> {
+    /// Another handle to the same values, only handed out inside a view borrowing `self`
+    fn alias(&self) -> Self {
+        VectorBuffer::builder()
+            .buffer(self.buffer.clone())
+            .offset(self.offset)
+            .inc(self.inc)
+            .len(self.len)
+            .build()
+    }
+}
+
+/// A matrix, that routines read transposed or conjugate transposed, see [`MatrixBuffer::t`] and [`MatrixBuffer::h`]
+///
+/// Routines taking a `TransposedView` derive their transpose flag from it,
+/// so `.a(a.t())` computes with `A^T` and `.a(&a)` with `A` itself.
+pub struct TransposedView<'a, T: OclPrm, L: MatrixLayout> {
+    pub(crate) matrix: &'a MatrixBuffer<T, L>,
+    pub(crate) transpose: MatrixTranspose,
+}
+
+impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
+    /// The transpose `A^T` of the matrix
+    pub fn t(&self) -> TransposedView<'_, T, L> {
+        TransposedView {
+            matrix: self,
+            transpose: MatrixTranspose::Yes,
+        }
+    }
+
+    /// The conjugate transpose (adjoint) `A^H` of the matrix
+    pub fn h(&self) -> TransposedView<'_, T, L> {
+        TransposedView {
+            matrix: self,
+            transpose: MatrixTranspose::Conjugate,
+        }
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> TransposedView<'a, T, L> {
+    /// The stored matrix, the transpose is not applied to it
+    pub fn matrix(&self) -> &'a MatrixBuffer<T, L> {
+        self.matrix
+    }
+
+    pub fn transpose(&self) -> MatrixTranspose {
+        self.transpose
+    }
+
+    /// Number of rows of the transposed matrix
+    pub fn rows(&self) -> usize {
+        self.shape().0
+    }
+
+    /// Number of columns of the transposed matrix
+    pub fn columns(&self) -> usize {
+        self.shape().1
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        self.transpose.apply(self.matrix.rows, self.matrix.columns)
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> From<&'a MatrixBuffer<T, L>> for TransposedView<'a, T, L> {
+    fn from(matrix: &'a MatrixBuffer<T, L>) -> Self {
+        TransposedView {
+            matrix,
+            transpose: MatrixTranspose::No,
+        }
+    }
+}
+
+impl<'a, 'b, T: OclPrm, L: MatrixLayout> From<&'a MatrixView<'b, T, L>>
+    for TransposedView<'a, T, L>
+{
+    fn from(view: &'a MatrixView<'b, T, L>) -> Self {
+        TransposedView::from(&view.matrix)
+    }
+}
+
+/// A batch of matrices, that routines read transposed or conjugate transposed,
+/// see [`BatchedMatrixBuffer::t`] and [`BatchedMatrixBuffer::h`]
+pub struct TransposedBatch<'a, T: OclPrm, L: MatrixLayout> {
+    pub(crate) batch: &'a BatchedMatrixBuffer<T, L>,
+    pub(crate) transpose: MatrixTranspose,
+}
+
+impl<T: OclPrm, L: MatrixLayout> BatchedMatrixBuffer<T, L> {
+    /// The transposes `A[i]^T` of all matrices of the batch
+    pub fn t(&self) -> TransposedBatch<'_, T, L> {
+        TransposedBatch {
+            batch: self,
+            transpose: MatrixTranspose::Yes,
+        }
+    }
+
+    /// The conjugate transposes (adjoints) `A[i]^H` of all matrices of the batch
+    pub fn h(&self) -> TransposedBatch<'_, T, L> {
+        TransposedBatch {
+            batch: self,
+            transpose: MatrixTranspose::Conjugate,
+        }
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> TransposedBatch<'a, T, L> {
+    /// The stored batch, the transpose is not applied to it
+    pub fn batch(&self) -> &'a BatchedMatrixBuffer<T, L> {
+        self.batch
+    }
+
+    pub fn transpose(&self) -> MatrixTranspose {
+        self.transpose
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> From<&'a BatchedMatrixBuffer<T, L>>
+    for TransposedBatch<'a, T, L>
+{
+    fn from(batch: &'a BatchedMatrixBuffer<T, L>) -> Self {
+        TransposedBatch {
+            batch,
+            transpose: MatrixTranspose::No,
+        }
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> Deref for MatrixView<'a, T, L> {
+    type Target = MatrixBuffer<T, L>;
+
+    fn deref(&self) -> &MatrixBuffer<T, L> {
+        &self.matrix
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> Deref for MatrixViewMut<'a, T, L> {
+    type Target = MatrixBuffer<T, L>;
+
+    fn deref(&self) -> &MatrixBuffer<T, L> {
+        &self.matrix
+    }
+}
+
+impl<'a, T: OclPrm, L: MatrixLayout> From<&'a mut MatrixBuffer<T, L>> for MatrixViewMut<'a, T, L> {
+    fn from(matrix: &'a mut MatrixBuffer<T, L>) -> Self {
+        MatrixViewMut {
+            matrix: matrix.alias(),
+            parent: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T: OclPrm, L: MatrixLayout> From<&'a mut MatrixViewMut<'b, T, L>>
+    for MatrixViewMut<'a, T, L>
+{
+    fn from(view: &'a mut MatrixViewMut<'b, T, L>) -> Self {
+        MatrixViewMut {
+            matrix: view.matrix.alias(),
+            parent: PhantomData,
+        }
+    }
+}
+
+impl<T: OclPrm, L: MatrixLayout> MatrixBuffer<T, L> {
+    /// Another handle to the same values, only handed out inside a view borrowing `self`
+    fn alias(&self) -> Self {
+        MatrixBuffer::builder()
+            .buffer(self.buffer.clone())
+            .rows(self.rows)
+            .columns(self.columns)
+            .offset(self.offset)
+            .stride(self.stride)
+            .layout(self.layout.clone())
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::gemm::{Gemm, RunGemm};
+    use crate::scal::{RunVectorScale, VectorScale};
+    use crate::syr2k::{RunSyr2k, Syr2k};
+    use crate::{
+        LayoutColMajor, LayoutRowMajor, MatrixBuffer, MatrixTranspose, Symmetric, TransposedView,
+    };
+    use ocl::ProQue;
+
+    #[test]
+    fn test_view_offsets() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let row_major = MatrixBuffer::new_default(&pro_que, 5, 4, 0.0f32, LayoutRowMajor);
+        let col_major = MatrixBuffer::new_default(&pro_que, 5, 4, 0.0f32, LayoutColMajor);
+
+        let block = row_major.view(1..3, 2..5);
+        assert_eq!((block.rows(), block.columns()), (2, 3));
+        assert_eq!((block.offset(), block.stride()), (7, 5));
+
+        let block = col_major.view(1..3, 2..5);
+        assert_eq!((block.offset(), block.stride()), (9, 4));
+
+        let nested = block.view(1..2, 1..3);
+        assert_eq!((nested.rows(), nested.columns()), (1, 2));
+        assert_eq!((nested.offset(), nested.stride()), (14, 4));
+
+        assert!(row_major.try_view(0..5, 0..5).is_err());
+    }
+
+    #[test]
+    fn test_vector_views() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let row_major = MatrixBuffer::new_default(&pro_que, 5, 4, 0.0f32, LayoutRowMajor);
+        let col_major = MatrixBuffer::new_default(&pro_que, 5, 4, 0.0f32, LayoutColMajor);
+
+        let row = row_major.row(2);
+        assert_eq!((row.offset(), row.inc(), row.len()), (10, 1, 5));
+        let column = row_major.column(3);
+        assert_eq!((column.offset(), column.inc(), column.len()), (3, 5, 4));
+        let diagonal = row_major.diagonal();
+        assert_eq!(
+            (diagonal.offset(), diagonal.inc(), diagonal.len()),
+            (0, 6, 4)
+        );
+
+        let row = col_major.row(2);
+        assert_eq!((row.offset(), row.inc(), row.len()), (2, 4, 5));
+        let column = col_major.column(3);
+        assert_eq!((column.offset(), column.inc(), column.len()), (12, 1, 4));
+        assert!(col_major.try_row(4).is_err());
+        assert!(col_major.try_column(5).is_err());
+
+        let block = row_major.view(1..3, 1..4);
+        let diagonal = block.diagonal();
+        assert_eq!(
+            (diagonal.offset(), diagonal.inc(), diagonal.len()),
+            (6, 6, 2)
+        );
+    }
+
+    #[test]
+    fn test_scale_column() {
+        let pro_que = ProQue::builder().src("").dims(6).build().unwrap();
+        let mut matrix = MatrixBuffer::new_default(&pro_que, 3, 2, 1.0f32, LayoutRowMajor);
+
+        let column = matrix.column_mut(1);
+        let task = VectorScale::builder()
+            .queue(&pro_que.queue())
+            .alpha(3.0)
+            .x_vector(&column)
+            .build();
+        unsafe { task.run().unwrap() };
+
+        let mut result = vec![0.0; 6];
+        matrix.buffer().read(&mut result).enq().unwrap();
+        assert_eq!(result, vec![1.0, 3.0, 1.0, 1.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn test_transposed_shape() {
+        let pro_que = ProQue::builder().src("").dims(20).build().unwrap();
+        let matrix = MatrixBuffer::new_default(&pro_que, 5, 4, 0.0f32, LayoutRowMajor);
+
+        let transposed = matrix.t();
+        assert_eq!((transposed.rows(), transposed.columns()), (5, 4));
+        assert_eq!(transposed.transpose(), MatrixTranspose::Yes);
+        assert_eq!(matrix.h().transpose(), MatrixTranspose::Conjugate);
+
+        let block = matrix.view(0..2, 0..3);
+        assert_eq!((block.t().rows(), block.t().columns()), (3, 2));
+        assert_eq!(
+            TransposedView::from(&block).transpose(),
+            MatrixTranspose::No
+        );
+    }
+
+    #[test]
+    fn test_gemm_on_blocks() {
+        let pro_que = ProQue::builder().src("").dims(16).build().unwrap();
+
+        let a = MatrixBuffer::new_default(&pro_que, 4, 4, 1.0f32, LayoutRowMajor);
+        let b = MatrixBuffer::new_default(&pro_que, 4, 4, 2.0f32, LayoutRowMajor);
+        let mut c = MatrixBuffer::new_default(&pro_que, 4, 4, 0.0f32, LayoutRowMajor);
+
+        // lower right 2x2 block of c = upper left block of a * upper right block of b
+        let a_block = a.view(0..2, 0..2);
+        let b_block = b.view(0..2, 2..4);
+        let mut c_block = c.view_mut(2..4, 2..4);
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(&a_block)
+            .b(&b_block)
+            .c(&mut c_block)
+            .build();
+        unsafe { task.run().unwrap() };
+
+        let mut result = vec![0.0; 16];
+        c.buffer().read(&mut result).enq().unwrap();
+        #[rustfmt::skip]
+        let expected = vec![
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 4.0, 4.0,
+            0.0, 0.0, 4.0, 4.0,
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_split_blocks() {
+        let pro_que = ProQue::builder().src("").dims(16).build().unwrap();
+        let mut matrix = MatrixBuffer::new_default(&pro_que, 4, 4, 1.0f32, LayoutRowMajor);
+
+        // A22 := A21 * A11, reading blocks of the left half while writing the right one
+        let (left, mut right) = matrix.split_columns_mut(2);
+        let a11 = left.view(0..2, 0..2);
+        let a21 = left.view(2..4, 0..2);
+        let mut a22 = right.view_mut(2..4, 0..2);
+        assert_eq!((a21.offset(), a22.offset()), (8, 10));
+        let task = Gemm::builder()
+            .queue(&pro_que.queue())
+            .a(&a21)
+            .b(&a11)
+            .c(&mut a22)
+            .build();
+        unsafe { task.run().unwrap() };
+
+        let mut result = vec![0.0; 16];
+        matrix.buffer().read(&mut result).enq().unwrap();
+        #[rustfmt::skip]
+        let expected = vec![
+            1.0, 1.0, 1.0, 1.0,
+            1.0, 1.0, 1.0, 1.0,
+            1.0, 1.0, 2.0, 2.0,
+            1.0, 1.0, 2.0, 2.0,
+        ];
+        assert_eq!(result, expected);
+
+        assert!(matrix.try_split_rows_mut(5).is_err());
+    }
+
+    #[test]
+    fn test_symmetric_block() {
+        let pro_que = ProQue::builder().src("").dims(16).build().unwrap();
+        let a = MatrixBuffer::new_default(&pro_que, 3, 2, 1.0f32, LayoutRowMajor);
+        let b = MatrixBuffer::new_default(&pro_que, 3, 2, 2.0f32, LayoutRowMajor);
+        let mut c = MatrixBuffer::new_default(&pro_que, 4, 4, 0.0f32, LayoutRowMajor);
+
+        // only the upper triangle of the lower right block is updated
+        let mut c_block = c.view_mut(2..4, 2..4);
+        let task = Syr2k::builder()
+            .queue(&pro_que.queue())
+            .a(&a)
+            .b(&b)
+            .c(Symmetric::upper(&mut c_block))
+            .build();
+        unsafe { task.run().unwrap() };
+
+        let mut result = vec![0.0; 16];
+        c.buffer().read(&mut result).enq().unwrap();
+        #[rustfmt::skip]
+        let expected = vec![
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 12.0, 12.0,
+            0.0, 0.0, 0.0, 12.0,
+        ];
+        assert_eq!(result, expected);
+    }
+}